@@ -0,0 +1,66 @@
+// Scalar multiplication on secp256k1, run with `cargo bench -p ec_cryptography`.
+//
+// Compares the double-and-add loop with the wNAF, the constant-time ladder, the generator
+// comb table and the Strauss multi-scalar multiplication used by `S256Field::verify`.
+
+use std::{hint::black_box, time::Instant};
+
+use ec_cryptography::{multi_scalar_mul, s256_field::secp_generator_point, Point, JacobianPoint};
+use finite_fields::{rng, Fe256, Scalar};
+use rug::Integer;
+
+const ITERATIONS: u32 = 200;
+
+// Prints the average time of `operation` over ITERATIONS runs
+fn bench<T>(name: &str, mut operation: impl FnMut() -> T) {
+    black_box(operation());
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(operation());
+    }
+    println!("{:<32} {:>10.1?}", name, start.elapsed() / ITERATIONS);
+}
+
+// The affine double-and-add loop, one inversion per group operation
+fn affine_double_and_add(point: &Point<Fe256>, scalar: &Integer) -> Point<Fe256> {
+    let mut result = point.identity();
+    for bit in (0..scalar.significant_bits()).rev() {
+        result = &result + &result;
+        if scalar.get_bit(bit) {
+            result = &result + point;
+        }
+    }
+    result
+}
+
+// The same loop in Jacobian coordinates
+fn double_and_add(point: &Point<Fe256>, scalar: &Integer) -> Point<Fe256> {
+    let mut result = JacobianPoint::from(&point.identity());
+    for bit in (0..scalar.significant_bits()).rev() {
+        result = result.double();
+        if scalar.get_bit(bit) {
+            result = result.add_mixed(point);
+        }
+    }
+    result.to_affine()
+}
+
+fn main() {
+    let mut rng = rng::deterministic(1);
+    let secret = Scalar::random(&mut rng);
+    let u = secret.to_integer();
+    let v = Scalar::random(&mut rng).to_integer();
+
+    let generator = Point::<Fe256>::try_from(&secp_generator_point()).unwrap();
+    let point = generator.scalar_mul(Integer::from(5000));
+
+    bench("affine double-and-add", || affine_double_and_add(&point, &u));
+    bench("jacobian double-and-add", || double_and_add(&point, &u));
+    bench("wnaf", || point.scalar_mul(u.clone()));
+    bench("constant-time ladder", || &point * &secret);
+    bench("generator double-and-add", || double_and_add(&generator, &u));
+    bench("generator comb", || generator.scalar_mul(u.clone()));
+    bench("uG + vP, two multiplications", || generator.scalar_mul(u.clone()) + point.scalar_mul(v.clone()));
+    bench("uG + vP, strauss", || multi_scalar_mul(&[(&u, &generator), (&v, &point)]));
+}
@@ -0,0 +1,327 @@
+// Counting the points of curves over small prime fields, and the orders of their points.
+//
+// `Curve::points` lists the whole group, which is only practical for toy fields. `Curve::order`
+// adds up Legendre symbols for fields up to 2^16: each x has 1 + ((x^3 + ax + b) / p) points.
+// Above that it uses Mestre's baby-step giant-step: by Hasse's theorem #E is within 2 sqrt(p)
+// of p + 1, each random point P gives a multiple of its order in that interval, and once the
+// lcm of a few orders has a single multiple in the interval, that multiple is #E. The points
+// of some curves never get there, but then those of the quadratic twist do (Mestre's theorem,
+// for p > 229), and the twist has 2p + 2 - #E points.
+
+use std::{collections::HashMap, sync::Arc};
+
+use finite_fields::{primality, rng::{self, RngCore}, FieldElement};
+use rug::Integer;
+
+use crate::{error::CurveError, Curve, Point};
+
+/// Fields up to this many bits are counted one x at a time
+const NAIVE_BITS: u32 = 16;
+
+/// Largest fields, in bits, that can be enumerated
+const ENUMERATION_BITS: u32 = 20;
+
+/// Largest fields, in bits, that can be counted
+const COUNTING_BITS: u32 = 48;
+
+/// Random points tried by the baby-step giant-step count, on the curve and then on its twist
+const ATTEMPTS: usize = 20;
+
+impl Curve {
+    /// x^3 + ax + b
+    fn right_hand_side(&self, x: &FieldElement) -> FieldElement {
+        x.clone() * x * x + self.a().clone() * x + self.b()
+    }
+
+    /// Every point of the curve, the identity first. Panics for fields above 2^20.
+    pub fn points(self: &Arc<Self>) -> Vec<Point> {
+        let prime = self.prime();
+        assert!(prime.significant_bits() <= ENUMERATION_BITS, "Enumerating points needs a field below 2^20");
+
+        let field = self.a().field().clone();
+        let mut points = vec![self.identity()];
+        let mut x = Integer::new();
+        while x < prime {
+            let x_element = field.element(x.clone());
+            if let Some(y) = self.right_hand_side(&x_element).sqrt() {
+                if !y.is_zero() {
+                    points.push(self.point(x_element.clone(), -y.clone()));
+                }
+                points.push(self.point(x_element, y));
+            }
+            x += 1;
+        }
+        points
+    }
+
+    /// The number of points #E, the identity included. Curves with a generator know it
+    /// already as n * h, other curves are counted, which panics for fields above 2^48.
+    pub fn order(self: &Arc<Self>) -> Integer {
+        if let (Some(order), Some(cofactor)) = (self.generator_order(), self.cofactor()) {
+            return order.clone() * cofactor;
+        }
+
+        let bits = self.prime().significant_bits();
+        assert!(bits <= COUNTING_BITS, "Counting points needs a field below 2^48");
+
+        if bits <= NAIVE_BITS {
+            return self.count_naive();
+        }
+        self.count_bsgs()
+            .or_else(|| {
+                let twist_order = self.twist().count_bsgs()?;
+                Some(self.prime() * Integer::from(2) + 2 - twist_order)
+            })
+            .expect("Neither the curve nor its twist pinned down the order")
+    }
+
+    fn count_naive(&self) -> Integer {
+        let prime = self.prime();
+        let field = self.a().field().clone();
+
+        let mut count = Integer::from(1);
+        let mut x = Integer::new();
+        while x < prime {
+            count += 1 + self.right_hand_side(&field.element(x.clone())).legendre();
+            x += 1;
+        }
+        count
+    }
+
+    /// Mestre's method, None if the random points never pinned down the order
+    fn count_bsgs(self: &Arc<Self>) -> Option<Integer> {
+        let prime = self.prime();
+        let width = Integer::from(&prime * 4).sqrt();
+        let (low, high) = (prime.clone() + 1 - &width, prime.clone() + 1 + &width);
+
+        let mut rng = rng::deterministic(0);
+        let mut lcm = Integer::from(1);
+        for _ in 0..ATTEMPTS {
+            let point = self.random_point(&mut rng);
+            let multiple = hasse_multiple(&point, &low, &width);
+            lcm.lcm_mut(&point.order_dividing(&multiple, &factors(&multiple)));
+
+            // The first multiple of the lcm in the interval, if it is the only one it is #E
+            let first = (Integer::from(&low + &lcm) - 1) / &lcm * &lcm;
+            if Integer::from(&first + &lcm) > high {
+                return Some(first);
+            }
+        }
+        None
+    }
+
+    /// The quadratic twist y^2 = x^3 + ad^2 x + bd^3 for a non-square d
+    fn twist(&self) -> Arc<Curve> {
+        let field = self.a().field().clone();
+        let mut d = field.element(Integer::from(2));
+        while d.legendre() != -1 {
+            d += 1u64;
+        }
+
+        let d_squared = d.clone() * &d;
+        Curve::new(self.a().clone() * &d_squared, self.b().clone() * &d_squared * &d)
+    }
+
+    fn random_point<R: RngCore>(self: &Arc<Self>, rng: &mut R) -> Point {
+        let field = self.a().field().clone();
+        loop {
+            let x = field.element(Integer::from(rng.next_u64()) % self.prime());
+            if let Some(y) = self.right_hand_side(&x).sqrt() {
+                return self.point(x, y);
+            }
+        }
+    }
+
+    /// The order n of `generator` and the cofactor h = #E / n, the parameters of
+    /// `Curve::with_generator`. Fails if n is not prime.
+    pub fn generator_parameters(self: &Arc<Self>, generator: &Point) -> Result<(Integer, Integer), CurveError> {
+        let order = generator.order();
+        if !primality::is_prime(&order) {
+            return Err(CurveError::CompositeOrder(order));
+        }
+
+        let cofactor = self.order() / &order;
+        Ok((order, cofactor))
+    }
+}
+
+impl Point {
+    /// The order of the point, the smallest n > 0 with nP = O
+    pub fn order(&self) -> Integer {
+        if self.curve.is_generator(self) {
+            return self.curve.generator_order().unwrap().clone();
+        }
+
+        // The order divides #E, and the generator order n of a named curve is prime
+        let group_order = self.curve.order();
+        let primes = match (self.curve.generator_order(), self.curve.cofactor()) {
+            (Some(order), Some(cofactor)) => {
+                let mut primes = factors(cofactor);
+                primes.push(order.clone());
+                primes
+            }
+            _ => factors(&group_order),
+        };
+        self.order_dividing(&group_order, &primes)
+    }
+
+    /// The order of the point, given a multiple of it and the primes dividing that multiple
+    fn order_dividing(&self, multiple: &Integer, primes: &[Integer]) -> Integer {
+        let mut order = multiple.clone();
+        for prime in primes {
+            while order.is_divisible(prime) && self.scalar_mul(order.clone() / prime).is_identity() {
+                order /= prime;
+            }
+        }
+        order
+    }
+}
+
+/// The distinct primes dividing n
+fn factors(n: &Integer) -> Vec<Integer> {
+    let factorization = primality::factorize(n).expect("Could not factor the group order");
+    factorization.into_iter().map(|(prime, _)| prime).collect()
+}
+
+/// The coordinates of a point, as a hash key
+pub(crate) fn key(point: &Point) -> Option<(Integer, Integer)> {
+    Some((point.x.as_ref()?.num(), point.y.as_ref()?.num()))
+}
+
+/// An M in [low, low + 2 width] with M * point = O, found with baby-step giant-step
+fn hasse_multiple(point: &Point, low: &Integer, width: &Integer) -> Integer {
+    // m = floor(sqrt(2 width + 1)) + 1 steps of each kind cover the interval
+    let mut steps = Integer::from(width * 2);
+    steps += 1;
+    steps.sqrt_mut();
+    steps += 1;
+    let step_count = steps.to_u64().unwrap();
+
+    // Baby steps: jP for 0 <= j < m
+    let mut baby_steps = HashMap::new();
+    let mut current = point.identity();
+    for j in 0..step_count {
+        baby_steps.entry(key(&current)).or_insert(j);
+        current = &current + point;
+    }
+
+    // Giant steps: -(low + im)P, a match with jP means (low + im + j)P = O
+    let giant_step = -current;
+    let mut target = -point.scalar_mul(low.clone());
+    for i in 0..=step_count {
+        if let Some(&j) = baby_steps.get(&key(&target)) {
+            return low.clone() + Integer::from(&steps * i) + j;
+        }
+        target = &target + &giant_step;
+    }
+    unreachable!("Hasse's theorem puts a multiple of the order in the interval")
+}
+
+/// The toy curves shared by the tests of the curve algorithms
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::{ops::Range, sync::{Arc, LazyLock}};
+
+    use finite_fields::{FieldElement, PrimeField};
+    use rug::{ops::RemRounding, Integer};
+
+    use crate::Curve;
+
+    /// GF(223), the field of the examples of Programming Bitcoin
+    static F223: LazyLock<Arc<PrimeField>> = LazyLock::new(|| PrimeField::new(Integer::from(223)).unwrap());
+
+    pub(crate) fn toy_curve(prime: u32, a: u32, b: u32) -> Arc<Curve> {
+        let field = PrimeField::new(Integer::from(prime)).unwrap();
+        Curve::new(field.element(Integer::from(a)), field.element(Integer::from(b)))
+    }
+
+    /// An element of GF(223)
+    pub(crate) fn element(num: u32) -> FieldElement {
+        F223.element(Integer::from(num))
+    }
+
+    /// The element num mod p, for negative num too
+    pub(crate) fn signed_element(field: &Arc<PrimeField>, num: i64) -> FieldElement {
+        field.element(Integer::from(num).rem_euc(field.prime()))
+    }
+
+    /// The points (x, y) of a toy curve for x in `xs`, where `y_squared` gives y^2 from x and
+    /// `point` builds the point of the curve model
+    pub(crate) fn points<P>(
+        field: &Arc<PrimeField>,
+        xs: Range<i64>,
+        y_squared: impl Fn(&FieldElement) -> FieldElement,
+        point: impl Fn(FieldElement, FieldElement) -> P,
+    ) -> Vec<P> {
+        xs.filter_map(|x| {
+            let x = signed_element(field, x);
+            let y = y_squared(&x).sqrt()?;
+            Some(point(x, y))
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::Integer;
+
+    use crate::{error::CurveError, s256_field::secp_generator_point, SECP256K1};
+
+    use super::fixtures::{element, toy_curve};
+
+    #[test]
+    fn test_points() {
+        let curve = toy_curve(223, 0, 7);
+        let points = curve.points();
+
+        assert_eq!(Integer::from(points.len()), curve.order());
+        assert!(points.contains(&curve.point(element(47), element(71))));
+        for (i, point) in points.iter().enumerate() {
+            assert!(point.is_valid());
+            assert!(!points[i + 1..].contains(point));
+            // Lagrange: every order divides #E
+            assert!(curve.order().is_divisible(&point.order()));
+        }
+    }
+
+    #[test]
+    fn test_point_order() {
+        let curve = toy_curve(223, 0, 7);
+        assert_eq!(curve.point(element(47), element(71)).order(), 21);
+        assert_eq!(curve.point(element(15), element(86)).order(), 7);
+        assert_eq!(curve.identity().order(), 1);
+
+        let point = secp_generator_point().scalar_mul(Integer::from(5000));
+        assert_eq!(point.order(), *SECP256K1.generator_order().unwrap());
+    }
+
+    #[test]
+    fn test_baby_step_giant_step() {
+        // Above 2^16 the count switches to baby-step giant-step
+        for (prime, a, b) in [(65537, 2, 3), (100003, 0, 7), (1000003, 5, 1)] {
+            let curve = toy_curve(prime, a, b);
+            assert_eq!(curve.count_bsgs().unwrap(), curve.count_naive(), "Wrong count for p = {}", prime);
+
+            // The curve and its twist have 2p + 2 points together
+            assert_eq!(curve.count_bsgs().unwrap() + curve.twist().count_bsgs().unwrap(), 2 * prime as u64 + 2);
+        }
+    }
+
+    #[test]
+    fn test_generator_parameters() {
+        let curve = toy_curve(223, 0, 7);
+        let order = curve.order();
+
+        let generator = curve.point(element(15), element(86));
+        assert_eq!(curve.generator_parameters(&generator), Ok((Integer::from(7), order / 7)));
+
+        let generator = curve.point(element(47), element(71));
+        assert_eq!(curve.generator_parameters(&generator), Err(CurveError::CompositeOrder(Integer::from(21))));
+
+        assert_eq!(
+            SECP256K1.generator_parameters(&secp_generator_point()),
+            Ok((SECP256K1.generator_order().unwrap().clone(), Integer::from(1)))
+        );
+    }
+}
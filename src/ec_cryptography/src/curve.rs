@@ -0,0 +1,267 @@
+// The parameters of a short Weierstrass curve y^2 = x^3 + ax + b.
+//
+// Points keep an `Arc<Curve>` instead of their own copy of a and b, so checking that two
+// points are on the same curve is usually a pointer comparison. The named curves are
+// statics, their hex constants are parsed once on first use.
+
+use std::sync::{Arc, LazyLock, OnceLock};
+
+use finite_fields::{Fe256, Field, FieldElement, FieldError, PrimeField};
+use rug::Integer;
+
+use crate::{helper::is_same_curve, multiplication::CombTable, Point};
+
+/// secp256k1, the Bitcoin curve y^2 = x^3 + 7
+pub static SECP256K1: LazyLock<Arc<Curve>> = LazyLock::new(|| {
+    let field = Fe256::field();
+
+    Curve::with_generator(
+        field.zero(),
+        field.element(Integer::from(7)),
+        (
+            field.element(hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")),
+            field.element(hex("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")),
+        ),
+        hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"),
+        Integer::from(1),
+    )
+});
+
+/// secp256r1 (NIST P-256), y^2 = x^3 - 3x + b
+pub static SECP256R1: LazyLock<Arc<Curve>> = LazyLock::new(|| {
+    let field = PrimeField::new(hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff")).unwrap();
+
+    Curve::with_generator(
+        -field.element(Integer::from(3)),
+        field.element(hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")),
+        (
+            field.element(hex("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296")),
+            field.element(hex("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5")),
+        ),
+        hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"),
+        Integer::from(1),
+    )
+});
+
+/// secp256k1 over the fixed-width Fe256 arithmetic, used for the points converted from `SECP256K1`
+pub(crate) static SECP256K1_FIXED_WIDTH: LazyLock<Arc<Curve<Fe256>>> =
+    LazyLock::new(|| Arc::new(Curve::try_from(&**SECP256K1).unwrap()));
+
+fn hex(digits: &str) -> Integer {
+    Integer::from_str_radix(digits, 16).unwrap()
+}
+
+/// A curve y^2 = x^3 + ax + b over the field F. Named curves also have a generator G
+/// of prime order n, and the cofactor h = #E / n.
+#[derive(Clone, Debug)]
+pub struct Curve<F = FieldElement> {
+    a: F,
+    b: F,
+    generator: Option<(F, F)>,
+    generator_order: Option<Integer>,
+    cofactor: Option<Integer>,
+    // Multiples of the generator for `scalar_mul`, built on first use
+    generator_table: OnceLock<CombTable<F>>,
+}
+
+/// Two curves are equal if they have the same field, a and b. The generator, its order and
+/// the cofactor are metadata: a point built with `Point::new` on y^2 = x^3 + 7 over the
+/// secp256k1 field is on the same curve as the points of `SECP256K1`.
+impl<F: Field> PartialEq for Curve<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a && self.b == other.b
+    }
+}
+
+impl<F: Field> Curve<F> {
+    /// A curve without a known generator, as used for toy curves and over the rationals
+    pub fn new(a: F, b: F) -> Arc<Curve<F>> {
+        Arc::new(Curve {
+            a,
+            b,
+            generator: None,
+            generator_order: None,
+            cofactor: None,
+            generator_table: OnceLock::new(),
+        })
+    }
+
+    pub fn with_generator(a: F, b: F, generator: (F, F), order: Integer, cofactor: Integer) -> Arc<Curve<F>> {
+        let curve = Curve {
+            a,
+            b,
+            generator: Some(generator),
+            generator_order: Some(order),
+            cofactor: Some(cofactor),
+            generator_table: OnceLock::new(),
+        };
+        let (x, y) = curve.generator.as_ref().unwrap();
+        assert!(curve.contains(x, y), "Generator is not on the curve");
+
+        Arc::new(curve)
+    }
+
+    pub fn a(&self) -> &F {
+        &self.a
+    }
+
+    pub fn b(&self) -> &F {
+        &self.b
+    }
+
+    /// The order n of the generator
+    pub fn generator_order(&self) -> Option<&Integer> {
+        self.generator_order.as_ref()
+    }
+
+    pub fn cofactor(&self) -> Option<&Integer> {
+        self.cofactor.as_ref()
+    }
+
+    /// True if (x, y) satisfies y^2 = x^3 + ax + b
+    pub fn contains(&self, x: &F, y: &F) -> bool {
+        y.clone() * y == x.clone() * x * x + self.a.clone() * x + &self.b
+    }
+
+    /// The generator G, if the curve has one
+    pub fn generator(self: &Arc<Self>) -> Option<Point<F>> {
+        let (x, y) = self.generator.clone()?;
+        Some(Point::on_curve(self, Some(x), Some(y)))
+    }
+
+    /// True if `point` is the generator of this curve
+    pub fn is_generator(&self, point: &Point<F>) -> bool {
+        match (&self.generator, &point.x, &point.y) {
+            (Some((x, y)), Some(point_x), Some(point_y)) => x == point_x && y == point_y,
+            _ => false,
+        }
+    }
+
+    /// The comb table of the generator, panics if the curve has none
+    pub(crate) fn generator_table(self: &Arc<Self>) -> &CombTable<F> {
+        self.generator_table.get_or_init(|| {
+            let generator = self.generator().expect("The curve has no generator");
+            CombTable::new(&generator, self.generator_order.as_ref().unwrap().significant_bits())
+        })
+    }
+
+    /// The point (x, y), panics if it is not on the curve
+    pub fn point(self: &Arc<Self>, x: F, y: F) -> Point<F> {
+        Point::on_curve(self, Some(x), Some(y))
+    }
+
+    /// The point at infinity
+    pub fn identity(self: &Arc<Self>) -> Point<F> {
+        Point::on_curve(self, None, None)
+    }
+
+    /// True if both are the same curve, without comparing the parameters when they share an Arc
+    pub fn is_same(this: &Arc<Self>, other: &Arc<Self>) -> bool {
+        is_same_curve(this, other)
+    }
+}
+
+impl Curve {
+    /// The prime p of the field of the coordinates
+    pub fn prime(&self) -> Integer {
+        self.a.order()
+    }
+}
+
+/// Moves a curve over the secp256k1 field to the fixed-width Fe256 arithmetic
+impl TryFrom<&Curve> for Curve<Fe256> {
+    type Error = FieldError;
+
+    fn try_from(curve: &Curve) -> Result<Curve<Fe256>, FieldError> {
+        let generator = match &curve.generator {
+            Some((x, y)) => Some((Fe256::try_from(x)?, Fe256::try_from(y)?)),
+            None => None,
+        };
+
+        Ok(Curve {
+            a: Fe256::try_from(&curve.a)?,
+            b: Fe256::try_from(&curve.b)?,
+            generator,
+            generator_order: curve.generator_order.clone(),
+            cofactor: curve.cofactor.clone(),
+            generator_table: OnceLock::new(),
+        })
+    }
+}
+
+impl From<&Curve<Fe256>> for Curve {
+    fn from(curve: &Curve<Fe256>) -> Curve {
+        Curve {
+            a: curve.a.into(),
+            b: curve.b.into(),
+            generator: curve.generator.map(|(x, y)| (x.into(), y.into())),
+            generator_order: curve.generator_order.clone(),
+            cofactor: curve.cofactor.clone(),
+            generator_table: OnceLock::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use finite_fields::{Fe256, FieldElement};
+    use rug::Integer;
+
+    use super::{hex, Curve, SECP256K1, SECP256K1_FIXED_WIDTH, SECP256R1};
+
+    use crate::{s256_field::secp_generator_point, Point};
+
+    #[test]
+    fn test_named_curves() {
+        for curve in [&*SECP256K1, &*SECP256R1] {
+            let generator = curve.generator().unwrap();
+            let order = curve.generator_order().unwrap().clone();
+
+            assert_eq!(generator.scalar_mul(order), curve.identity());
+            assert_eq!(curve.cofactor(), Some(&Integer::from(1)));
+        }
+        assert_eq!(SECP256K1.prime(), Fe256::modulus());
+
+        // 2G on P-256
+        let field = SECP256R1.a().field().clone();
+        let double = SECP256R1.point(
+            field.element(hex("7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978")),
+            field.element(hex("07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1")),
+        );
+        assert_eq!(SECP256R1.generator().unwrap().scalar_mul(Integer::from(2)), double);
+
+        assert_eq!(Curve::from(&**SECP256K1_FIXED_WIDTH), **SECP256K1);
+    }
+
+    #[test]
+    fn test_same_curve() {
+        let element = |num: u32| FieldElement::new(Integer::from(num), Integer::from(223));
+        let curve = Curve::new(element(0), element(7));
+        let copy = Arc::new((*curve).clone());
+
+        assert!(Curve::is_same(&curve, &curve));
+        assert!(Curve::is_same(&curve, &copy));
+        assert!(!Curve::is_same(&curve, &Curve::new(element(0), element(5))));
+        assert!(!Curve::is_same(&SECP256K1, &SECP256R1));
+
+        // Points on equal curves can be added even if they were created separately
+        let point = curve.point(element(47), element(71));
+        assert_eq!(&point + &copy.point(element(47), element(71)), point.scalar_mul(Integer::from(2)));
+
+        // A curve without a generator is the same as the named curve with the same a and b
+        let generator = secp_generator_point();
+        let field = SECP256K1.a().field().clone();
+        let point = Point::new(generator.x().cloned(), generator.y().cloned(), field.zero(), field.element(Integer::from(7)));
+        assert!(Curve::is_same(point.curve(), &SECP256K1));
+        assert_eq!(point, generator);
+        assert_eq!(&point + &generator, generator.scalar_mul(Integer::from(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Points are not on the same curve")]
+    fn test_different_curves() {
+        let _ = SECP256K1.generator().unwrap() + SECP256R1.generator().unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+// Discrete logarithms on toy curves: given P and Q = kP, find k.
+//
+// Baby-step giant-step stores sqrt(n) points and needs about as many additions. Pollard's rho
+// needs about the same number of steps with constant memory: a pseudo-random walk mixing
+// aP + bQ eventually repeats, and the two ways of writing the repeated point give k. Both cost
+// sqrt(n), about 2^128 group operations for secp256k1. Pohlig-Hellman splits the problem along
+// the factors of n, so its cost is set by the largest prime factor: a smooth order is weak
+// however large it is, which is why named curves use a generator of prime order.
+
+use std::collections::HashMap;
+
+use finite_fields::{primality, rng::{self, RngCore}};
+use rug::{ops::{Pow, RemRounding}, Integer};
+
+use crate::{counting::key, error::CurveError, Curve, Point};
+
+/// Largest orders, in bits, for baby-step giant-step, which stores 2^(bits / 2) points
+const BSGS_BITS: u32 = 40;
+
+/// Largest orders, in bits, for Pollard's rho
+const RHO_BITS: u32 = 64;
+
+/// Walks started by Pollard's rho before giving up
+const RHO_ATTEMPTS: u64 = 10;
+
+/// k in [0, n) with kP = Q, where n is the order of P, using baby-step giant-step
+pub fn dlog_bsgs(base: &Point, target: &Point) -> Result<Integer, CurveError> {
+    let order = subgroup_order(base, target)?;
+    bsgs(base, target, &order)
+}
+
+/// k in [0, n) with kP = Q, where n is the order of P, using Pollard's rho
+pub fn dlog_pollard_rho(base: &Point, target: &Point) -> Result<Integer, CurveError> {
+    let order = subgroup_order(base, target)?;
+    rho(base, target, &order)
+}
+
+/// k in [0, n) with kP = Q, where n is the order of P, solving one prime factor of n at a time
+pub fn dlog_pohlig_hellman(base: &Point, target: &Point) -> Result<Integer, CurveError> {
+    let order = subgroup_order(base, target)?;
+    let factorization = primality::factorize(&order).ok_or_else(|| CurveError::Unfactored(order.clone()))?;
+
+    // k mod q^e for each prime power, combined with the Chinese remainder theorem
+    let (mut logarithm, mut modulus) = (Integer::new(), Integer::from(1));
+    for (prime, exponent) in factorization {
+        let power = Integer::from((&prime).pow(exponent));
+        let residue = prime_power_dlog(base, target, &order, &prime, exponent)?;
+
+        // logarithm + modulus * t = residue (mod power)
+        let t = Integer::from(&residue - &logarithm) * modulus.clone().invert(&power).unwrap() % &power;
+        logarithm += t.rem_euc(&power) * &modulus;
+        modulus *= power;
+    }
+    Ok(logarithm)
+}
+
+/// The order n of the base, or an error if the target is not a multiple of the base
+fn subgroup_order(base: &Point, target: &Point) -> Result<Integer, CurveError> {
+    assert!(Curve::is_same(&base.curve, &target.curve), "Points are not on the same curve");
+
+    let order = base.order();
+    if !target.scalar_mul(order.clone()).is_identity() {
+        return Err(CurveError::NotInSubgroup);
+    }
+    Ok(order)
+}
+
+/// The logarithm mod q^e, one base-q digit at a time in the subgroup of order q
+fn prime_power_dlog(base: &Point, target: &Point, order: &Integer, prime: &Integer, exponent: u32) -> Result<Integer, CurveError> {
+    let subgroup_base = base.scalar_mul(Integer::from(order / prime));
+
+    let (mut logarithm, mut power) = (Integer::new(), Integer::from(1));
+    for i in 1..=exponent {
+        // (n / q^i)(Q - xP) is the next digit times the subgroup base
+        let remaining = target + &-base.scalar_mul(logarithm.clone());
+        let cofactor = Integer::from(order / &Integer::from(prime.pow(i)));
+        let digit_target = remaining.scalar_mul(cofactor);
+
+        let digit = if prime.significant_bits() <= BSGS_BITS {
+            bsgs(&subgroup_base, &digit_target, prime)?
+        } else {
+            rho(&subgroup_base, &digit_target, prime)?
+        };
+        logarithm += digit * &power;
+        power *= prime;
+    }
+    Ok(logarithm)
+}
+
+fn bsgs(base: &Point, target: &Point, order: &Integer) -> Result<Integer, CurveError> {
+    if order.significant_bits() > BSGS_BITS {
+        return Err(CurveError::OrderTooLarge(order.significant_bits()));
+    }
+
+    // m = floor(sqrt(n)) + 1 steps of each kind cover [0, n)
+    let mut steps = order.clone().sqrt();
+    steps += 1;
+    let step_count = steps.to_u64().unwrap();
+
+    // Baby steps: jP for 0 <= j < m
+    let mut baby_steps = HashMap::new();
+    let mut current = base.identity();
+    for j in 0..step_count {
+        baby_steps.entry(key(&current)).or_insert(j);
+        current = &current + base;
+    }
+
+    // Giant steps: Q - imP, a match with jP means Q = (im + j)P
+    let giant_step = -current;
+    let mut remaining = target.clone();
+    for i in 0..step_count {
+        if let Some(&j) = baby_steps.get(&key(&remaining)) {
+            return Ok((Integer::from(&steps * i) + j) % order);
+        }
+        remaining = &remaining + &giant_step;
+    }
+    Err(CurveError::NotInSubgroup)
+}
+
+// A point of the rho walk, aP + bQ
+#[derive(Clone)]
+struct WalkPoint {
+    point: Point,
+    a: Integer,
+    b: Integer,
+}
+
+impl WalkPoint {
+    /// The next point of the walk, chosen by x mod 3: add P, double, or add Q
+    fn step(&self, base: &Point, target: &Point, order: &Integer) -> WalkPoint {
+        let partition = match &self.point.x {
+            Some(x) => x.num().mod_u(3),
+            None => 0,
+        };
+        let (point, a, b) = match partition {
+            0 => (&self.point + base, Integer::from(&self.a + 1), self.b.clone()),
+            1 => (&self.point + &self.point, Integer::from(&self.a * 2), Integer::from(&self.b * 2)),
+            _ => (&self.point + target, self.a.clone(), Integer::from(&self.b + 1)),
+        };
+        WalkPoint { point, a: a % order, b: b % order }
+    }
+}
+
+fn rho(base: &Point, target: &Point, order: &Integer) -> Result<Integer, CurveError> {
+    if order.significant_bits() > RHO_BITS {
+        return Err(CurveError::OrderTooLarge(order.significant_bits()));
+    }
+    if *order == 1 {
+        return Ok(Integer::new());
+    }
+
+    for attempt in 0..RHO_ATTEMPTS {
+        let mut rng = rng::deterministic(attempt);
+        let a = Integer::from(rng.next_u64()) % order;
+        let b = Integer::from(rng.next_u64()) % order;
+        let point = base.scalar_mul(a.clone()) + target.scalar_mul(b.clone());
+
+        // Floyd's cycle finding: the hare walks twice as fast as the tortoise
+        let mut tortoise = WalkPoint { point, a, b };
+        let mut hare = tortoise.step(base, target, order);
+        while tortoise.point != hare.point {
+            tortoise = tortoise.step(base, target, order);
+            hare = hare.step(base, target, order).step(base, target, order);
+        }
+
+        if let Some(logarithm) = solve_collision(base, target, order, &tortoise, &hare) {
+            return Ok(logarithm);
+        }
+    }
+    Err(CurveError::NoCollision)
+}
+
+/// k from a1 P + b1 Q = a2 P + b2 Q, that is (b2 - b1) k = a1 - a2 (mod n). For a composite n
+/// there are gcd(b2 - b1, n) candidates, checked one by one.
+fn solve_collision(base: &Point, target: &Point, order: &Integer, first: &WalkPoint, second: &WalkPoint) -> Option<Integer> {
+    let coefficient = Integer::from(&second.b - &first.b).rem_euc(order);
+    let constant = Integer::from(&first.a - &second.a).rem_euc(order);
+
+    let divisor = coefficient.clone().gcd(order);
+    if divisor == *order || !constant.is_divisible(&divisor) || divisor.significant_bits() > 16 {
+        return None;
+    }
+
+    let reduced_order = Integer::from(order / &divisor);
+    let inverse = Integer::from(&coefficient / &divisor).invert(&reduced_order).ok()?;
+    let first_candidate = constant / &divisor * inverse % &reduced_order;
+
+    let mut candidate = first_candidate;
+    while candidate < *order {
+        if base.scalar_mul(candidate.clone()) == *target {
+            return Some(candidate);
+        }
+        candidate += &reduced_order;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::{rng::{self, RngCore}, PrimeField};
+    use rug::Integer;
+
+    use crate::{counting::fixtures::element, error::CurveError, s256_field::secp_generator_point, Curve};
+
+    use super::{dlog_bsgs, dlog_pohlig_hellman, dlog_pollard_rho};
+
+    #[test]
+    fn test_toy_curve() {
+        let curve = Curve::new(element(0), element(7));
+
+        // (47, 71) has order 21, (15, 86) order 7
+        for (base, order) in [(curve.point(element(47), element(71)), 21), (curve.point(element(15), element(86)), 7)] {
+            for k in 0..order {
+                let target = base.scalar_mul(Integer::from(k));
+
+                assert_eq!(dlog_bsgs(&base, &target), Ok(Integer::from(k)));
+                assert_eq!(dlog_pollard_rho(&base, &target), Ok(Integer::from(k)));
+                assert_eq!(dlog_pohlig_hellman(&base, &target), Ok(Integer::from(k)));
+            }
+        }
+
+        // (47, 71) is not a multiple of (15, 86)
+        let (base, target) = (curve.point(element(15), element(86)), curve.point(element(47), element(71)));
+        assert_eq!(dlog_bsgs(&base, &target), Err(CurveError::NotInSubgroup));
+        assert_eq!(dlog_pollard_rho(&base, &target), Err(CurveError::NotInSubgroup));
+        assert_eq!(dlog_pohlig_hellman(&base, &target), Err(CurveError::NotInSubgroup));
+    }
+
+    #[test]
+    fn test_larger_curve() {
+        let field = PrimeField::new(Integer::from(100003)).unwrap();
+        let curve = Curve::new(field.element(Integer::from(2)), field.element(Integer::from(3)));
+
+        // The first point with x >= 10
+        let x = (10..).map(|x| field.element(Integer::from(x))).find(|x| (x.clone() * x * x + curve.a().clone() * x + curve.b()).is_square()).unwrap();
+        let y = (x.clone() * &x * &x + curve.a().clone() * &x + curve.b()).sqrt().unwrap();
+        let base = curve.point(x, y);
+        let order = base.order();
+        assert!(order > 1000);
+
+        let mut rng = rng::deterministic(3);
+        for _ in 0..3 {
+            let k = Integer::from(rng.next_u64()) % &order;
+            let target = base.scalar_mul(k.clone());
+
+            assert_eq!(dlog_bsgs(&base, &target).as_ref(), Ok(&k));
+            assert_eq!(dlog_pollard_rho(&base, &target).as_ref(), Ok(&k));
+            assert_eq!(dlog_pohlig_hellman(&base, &target).as_ref(), Ok(&k));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        // The order of G is a 256-bit prime, there is nothing to split
+        let generator = secp_generator_point();
+        let target = generator.scalar_mul(Integer::from(5000));
+
+        assert_eq!(dlog_bsgs(&generator, &target), Err(CurveError::OrderTooLarge(256)));
+        assert_eq!(dlog_pollard_rho(&generator, &target), Err(CurveError::OrderTooLarge(256)));
+        assert_eq!(dlog_pohlig_hellman(&generator, &target), Err(CurveError::OrderTooLarge(256)));
+    }
+}
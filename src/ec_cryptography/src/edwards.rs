@@ -0,0 +1,278 @@
+// Curves in twisted Edwards form ax^2 + y^2 = 1 + dx^2y^2, the model of Ed25519.
+//
+// The identity is the affine point (0, 1) and one formula adds any two points. When a is a
+// square and d is not, as for Ed25519, the denominators never vanish and the law is complete.
+// The curve is birationally equivalent to the Montgomery curve with A = 2(a + d) / (a - d) and
+// B = 4 / (a - d) through u = (1 + y) / (1 - y), v = u / x, and from there to short
+// Weierstrass form. The map is defined on every Edwards point, (0, 1) and (0, -1) going to
+// the identity and to (0, 0), but the Montgomery points with v = 0 or u = -1 only have an
+// Edwards image when d is a square, at points at infinity that this affine model leaves out.
+
+use std::{fmt::Debug, ops::{Add, Neg}, sync::Arc};
+
+use finite_fields::FieldElement;
+use rug::Integer;
+
+use crate::{helper::{double_and_add, is_same_curve}, montgomery::{MontgomeryCurve, MontgomeryPoint}, Point};
+
+/// A curve ax^2 + y^2 = 1 + dx^2y^2 over a prime field with p > 3
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdwardsCurve {
+    a: FieldElement,
+    d: FieldElement,
+    // The birationally equivalent Montgomery curve
+    montgomery: Arc<MontgomeryCurve>,
+}
+
+impl EdwardsCurve {
+    /// Panics unless a and d are distinct and non-zero
+    pub fn new(a: FieldElement, d: FieldElement) -> Arc<EdwardsCurve> {
+        assert!(!a.is_zero() && !d.is_zero() && a != d, "The curve is singular");
+
+        let difference = a.clone() - &d;
+        let montgomery = MontgomeryCurve::new(
+            (a.clone() + &d) * 2u64 / &difference,
+            a.field().element(Integer::from(4)) / &difference,
+        );
+        Arc::new(EdwardsCurve { a, d, montgomery })
+    }
+
+    pub fn a(&self) -> &FieldElement {
+        &self.a
+    }
+
+    pub fn d(&self) -> &FieldElement {
+        &self.d
+    }
+
+    /// The Montgomery curve the points convert to
+    pub fn montgomery(&self) -> &Arc<MontgomeryCurve> {
+        &self.montgomery
+    }
+
+    /// True if (x, y) satisfies ax^2 + y^2 = 1 + dx^2y^2
+    pub fn contains(&self, x: &FieldElement, y: &FieldElement) -> bool {
+        let (xx, yy) = (x.clone() * x, y.clone() * y);
+        self.a.clone() * &xx + &yy == self.d.clone() * &xx * &yy + 1u64
+    }
+
+    /// The point (x, y), panics if it is not on the curve
+    pub fn point(self: &Arc<Self>, x: FieldElement, y: FieldElement) -> EdwardsPoint {
+        assert!(self.contains(&x, &y), "Point is not on the curve");
+        EdwardsPoint { x, y, curve: self.clone() }
+    }
+
+    /// The neutral point (0, 1)
+    pub fn identity(self: &Arc<Self>) -> EdwardsPoint {
+        EdwardsPoint { x: self.a.field().zero(), y: self.a.field().one(), curve: self.clone() }
+    }
+
+    fn is_same(this: &Arc<Self>, other: &Arc<Self>) -> bool {
+        is_same_curve(this, other)
+    }
+}
+
+/// A point on a twisted Edwards curve
+#[derive(Clone)]
+pub struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    curve: Arc<EdwardsCurve>,
+}
+
+impl Debug for EdwardsPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "EdwardsPoint({:?}, {:?})", self.x, self.y)
+    }
+}
+
+impl EdwardsPoint {
+    pub fn x(&self) -> &FieldElement {
+        &self.x
+    }
+
+    pub fn y(&self) -> &FieldElement {
+        &self.y
+    }
+
+    pub fn curve(&self) -> &Arc<EdwardsCurve> {
+        &self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.curve.a.field().one()
+    }
+
+    /// The same point on the Montgomery curve, ((1 + y) / (1 - y), (1 + y) / ((1 - y)x))
+    pub fn to_montgomery(&self) -> MontgomeryPoint {
+        let curve = &self.curve.montgomery;
+        if self.x.is_zero() {
+            // (0, 1) is the identity and (0, -1) the point of order 2
+            if self.is_identity() {
+                return curve.identity();
+            }
+            return curve.point(self.x.clone(), self.x.clone());
+        }
+
+        let u = (self.y.clone() + 1u64) / (-self.y.clone() + 1u64);
+        let v = u.clone() / &self.x;
+        curve.point(u, v)
+    }
+
+    /// The point of `curve` that converts to `point`, which must be on `curve.montgomery()`.
+    /// None for the points with v = 0 or u = -1, which have no affine image.
+    pub fn from_montgomery(curve: &Arc<EdwardsCurve>, point: &MontgomeryPoint) -> Option<EdwardsPoint> {
+        assert!(
+            MontgomeryCurve::is_same(point.curve(), &curve.montgomery),
+            "Point is not on the Montgomery form of the curve"
+        );
+
+        let (u, v) = match (point.x(), point.y()) {
+            (Some(u), Some(v)) => (u, v),
+            _ => return Some(curve.identity()),
+        };
+        if u.is_zero() {
+            return Some(curve.point(u.clone(), -curve.a.field().one()));
+        }
+        if v.is_zero() || (u.clone() + 1u64).is_zero() {
+            return None;
+        }
+
+        // x = u / v, y = (u - 1) / (u + 1)
+        Some(curve.point(u.clone() / v, (u.clone() - 1u64) / (u.clone() + 1u64)))
+    }
+
+    /// The same point on the short Weierstrass curve, through the Montgomery form
+    pub fn to_weierstrass(&self) -> Point {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    /// The point of `curve` that converts to `point`, which must be on the Weierstrass form
+    /// of `curve.montgomery()`. None if it has no affine image.
+    pub fn from_weierstrass(curve: &Arc<EdwardsCurve>, point: &Point) -> Option<EdwardsPoint> {
+        EdwardsPoint::from_montgomery(curve, &MontgomeryPoint::from_weierstrass(&curve.montgomery, point))
+    }
+
+    /// scalar * self, by double-and-add
+    pub fn scalar_mul(&self, scalar: Integer) -> EdwardsPoint {
+        double_and_add(self, scalar, self.curve.identity())
+    }
+}
+
+impl PartialEq for EdwardsPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && EdwardsCurve::is_same(&self.curve, &other.curve)
+    }
+}
+
+impl Eq for EdwardsPoint {}
+
+/// Panics if the sum is a point at infinity, which needs d to be a square
+impl Add<&EdwardsPoint> for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn add(self, other: &EdwardsPoint) -> EdwardsPoint {
+        assert!(EdwardsCurve::is_same(&self.curve, &other.curve), "Points are not on the same curve");
+        let (x1, y1, x2, y2) = (&self.x, &self.y, &other.x, &other.y);
+
+        // x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2), y3 = (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2)
+        let (xx, yy) = (x1.clone() * x2, y1.clone() * y2);
+        let dxxyy = self.curve.d.clone() * &xx * &yy;
+        let (x_denominator, y_denominator) = (dxxyy.clone() + 1u64, -dxxyy + 1u64);
+        assert!(
+            !x_denominator.is_zero() && !y_denominator.is_zero(),
+            "The sum is a point at infinity of the Edwards curve"
+        );
+
+        let x3 = (x1.clone() * y2 + y1.clone() * x2) / x_denominator;
+        let y3 = (yy - self.curve.a.clone() * &xx) / y_denominator;
+        EdwardsPoint { x: x3, y: y3, curve: self.curve.clone() }
+    }
+}
+
+impl Add for EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn add(self, other: EdwardsPoint) -> EdwardsPoint {
+        &self + &other
+    }
+}
+
+impl Neg for &EdwardsPoint {
+    type Output = EdwardsPoint;
+
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint { x: -self.x.clone(), y: self.y.clone(), curve: self.curve.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use finite_fields::PrimeField;
+    use rug::{ops::Pow, Integer};
+
+    use crate::counting::fixtures::{self, signed_element};
+
+    use super::{EdwardsCurve, EdwardsPoint};
+
+    // The points of a toy curve with 0 <= x < 60, y^2 = (1 - ax^2) / (1 - dx^2)
+    fn points(curve: &Arc<EdwardsCurve>) -> Vec<EdwardsPoint> {
+        fixtures::points(
+            curve.a().field(),
+            0..60,
+            |x| {
+                let xx = x.clone() * x;
+                (-(curve.a().clone() * &xx) + 1u64) / (-(curve.d().clone() * &xx) + 1u64)
+            },
+            |x, y| curve.point(x, y),
+        )
+    }
+
+    #[test]
+    fn test_addition_commutes_with_maps() {
+        // a = -1 is a square mod 1009 and d = 11 is not, so the addition law is complete
+        let field = PrimeField::new(Integer::from(1009)).unwrap();
+        let curve = EdwardsCurve::new(-field.one(), signed_element(&field, 11));
+        let points = points(&curve);
+        assert!(points.len() > 10);
+
+        for p in points.iter() {
+            let montgomery = p.to_montgomery();
+            assert_eq!(EdwardsPoint::from_montgomery(&curve, &montgomery).as_ref(), Some(p));
+            assert_eq!(EdwardsPoint::from_weierstrass(&curve, &p.to_weierstrass()).as_ref(), Some(p));
+
+            for q in points.iter().chain([&-p, &curve.identity()]) {
+                assert_eq!((p + q).to_montgomery(), &montgomery + &q.to_montgomery());
+                assert_eq!((p + q).to_weierstrass(), &p.to_weierstrass() + &q.to_weierstrass());
+            }
+        }
+
+        // (0, -1) has order 2 and maps to (0, 0)
+        let two_torsion = curve.point(field.zero(), -field.one());
+        assert_eq!(&two_torsion + &two_torsion, curve.identity());
+        assert_eq!(two_torsion.to_montgomery(), curve.montgomery().point(field.zero(), field.zero()));
+        assert_eq!(points[3].scalar_mul(Integer::from(7)).to_weierstrass(), points[3].to_weierstrass().scalar_mul(Integer::from(7)));
+    }
+
+    #[test]
+    fn test_ed25519() {
+        let field = PrimeField::new(Integer::from(2).pow(255) - 19).unwrap();
+        let element = |num: i64| signed_element(&field, num);
+
+        // -x^2 + y^2 = 1 - (121665 / 121666) x^2 y^2
+        let curve = EdwardsCurve::new(element(-1), element(-121665) / element(121666));
+        let base = curve.point(
+            field.element(Integer::from_str_radix("15112221349535400772501151409588531511454012693041857206046113283949847762202", 10).unwrap()),
+            element(4) / element(5),
+        );
+
+        // The base point of Curve25519 has u = 9
+        assert_eq!(base.to_montgomery().x(), Some(&element(9)));
+
+        let double = &base + &base;
+        assert_eq!(double.to_weierstrass(), &base.to_weierstrass() + &base.to_weierstrass());
+        assert_eq!((&double + &base).to_montgomery(), double.to_montgomery() + base.to_montgomery());
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt::{Display, Formatter};
+
+use rug::Integer;
+
+/// Errors returned by the curve algorithms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    /// A point chosen as a generator has an order that is not prime
+    CompositeOrder(Integer),
+    /// The target of a discrete logarithm is not a multiple of the base
+    NotInSubgroup,
+    /// The group is too large for the algorithm, with the bits of its order
+    OrderTooLarge(u32),
+    /// Pollard's rho ran out of walks without a usable collision
+    NoCollision,
+    /// The order could not be factored for Pohlig-Hellman
+    Unfactored(Integer),
+}
+
+impl Display for CurveError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CurveError::CompositeOrder(order) => write!(f, "The generator has order {}, which is not prime", order),
+            CurveError::NotInSubgroup => write!(f, "The target is not a multiple of the base"),
+            CurveError::OrderTooLarge(bits) => write!(f, "A {}-bit order is too large for this algorithm", bits),
+            CurveError::NoCollision => write!(f, "Pollard's rho found no usable collision"),
+            CurveError::Unfactored(order) => write!(f, "Could not factor the order {}", order),
+        }
+    }
+}
+
+impl std::error::Error for CurveError {}
+
+/// Errors returned by `Signature::from_der`, one per rule of BIP 66, and by `Signature::to_der`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DerError {
+    /// A signature has between 8 and 72 bytes
+    InvalidLength(usize),
+    /// The signature does not start with 0x30
+    NotASequence,
+    /// The length of the sequence or of an integer does not match the data
+    SequenceLength,
+    /// r or s does not start with 0x02
+    NotAnInteger,
+    /// r or s has no bytes
+    EmptyInteger,
+    /// r or s has its high bit set, or is negative
+    NegativeInteger,
+    /// r or s starts with a zero byte that is not needed
+    ExcessivePadding,
+    /// There are bytes after s
+    TrailingBytes,
+    /// r or s needs more than the 33 bytes a DER integer of a signature can have
+    IntegerTooLarge,
+}
+
+impl Display for DerError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            DerError::InvalidLength(length) => write!(f, "A DER signature cannot have {} bytes", length),
+            DerError::NotASequence => write!(f, "A DER signature starts with 0x30"),
+            DerError::SequenceLength => write!(f, "The DER lengths do not match the data"),
+            DerError::NotAnInteger => write!(f, "A DER integer starts with 0x02"),
+            DerError::EmptyInteger => write!(f, "A DER integer cannot be empty"),
+            DerError::NegativeInteger => write!(f, "r and s cannot be negative"),
+            DerError::ExcessivePadding => write!(f, "A DER integer cannot start with an unneeded zero byte"),
+            DerError::TrailingBytes => write!(f, "There are bytes after the DER signature"),
+            DerError::IntegerTooLarge => write!(f, "r and s cannot be longer than 33 bytes in DER"),
+        }
+    }
+}
+
+impl std::error::Error for DerError {}
@@ -1,3 +1,6 @@
+use std::{ops::{Add, Neg}, sync::Arc};
+
+use rug::Integer;
 use sha256::digest;
 
 pub fn double_hash(data: &str) -> Vec<u8> {
@@ -9,4 +12,30 @@ pub fn double_hash(data: &str) -> Vec<u8> {
     println!("hash: {:?}", hash);
 
     hash.into_bytes()
-}
\ No newline at end of file
+}
+
+/// True if both Arcs hold the same curve, without comparing the parameters when they are the
+/// same allocation
+pub(crate) fn is_same_curve<C: PartialEq>(this: &Arc<C>, other: &Arc<C>) -> bool {
+    Arc::ptr_eq(this, other) || **this == **other
+}
+
+/// scalar * point by double-and-add, for the curve models that have no faster method.
+/// A negative scalar multiplies -point.
+pub(crate) fn double_and_add<P>(point: &P, scalar: Integer, identity: P) -> P
+where
+    P: Clone,
+    for<'a> &'a P: Add<&'a P, Output = P> + Neg<Output = P>,
+{
+    let point = if scalar < 0 { -point } else { point.clone() };
+    let scalar = scalar.abs();
+
+    let mut result = identity;
+    for bit in (0..scalar.significant_bits()).rev() {
+        result = &result + &result;
+        if scalar.get_bit(bit) {
+            result = &result + &point;
+        }
+    }
+    result
+}
@@ -0,0 +1,297 @@
+// Jacobian coordinates for the curve y^2 = x^3 + ax + b.
+//
+// The triple (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3), and Z = 0 is the point
+// at infinity. Adding and doubling in this form only needs multiplications, the single field
+// inversion happens when converting back to affine coordinates. `Point::scalar_mul`
+// runs on these formulas.
+
+use std::{fmt::Debug, ops::{Add, Neg}, sync::Arc};
+
+use finite_fields::Field;
+
+use crate::{Curve, Point};
+
+/// A point in Jacobian coordinates
+#[derive(Clone)]
+pub struct JacobianPoint<F> {
+    x: F,
+    y: F,
+    z: F,
+    curve: Arc<Curve<F>>,
+}
+
+impl<F: Field> Debug for JacobianPoint<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.is_identity() {
+            write!(f, "JacobianPoint(infinity)")
+        } else {
+            write!(f, "JacobianPoint({:?}, {:?}, {:?})", self.x, self.y, self.z)
+        }
+    }
+}
+
+impl<F: Field> JacobianPoint<F> {
+    /// The point at infinity on `curve`
+    pub fn identity(curve: &Arc<Curve<F>>) -> JacobianPoint<F> {
+        let one = curve.a().one();
+
+        JacobianPoint {
+            x: one.clone(),
+            y: one,
+            z: curve.a().zero(),
+            curve: curve.clone(),
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Converts back to affine coordinates, this costs one field inversion
+    pub fn to_affine(&self) -> Point<F> {
+        let z_inverse = match self.z.inv() {
+            Some(z_inverse) => z_inverse,
+            None => return self.curve.identity(),
+        };
+        let z_inverse_squared = z_inverse.clone() * &z_inverse;
+
+        let x = self.x.clone() * &z_inverse_squared;
+        let y = self.y.clone() * &z_inverse_squared * z_inverse;
+
+        Point {
+            x: Some(x),
+            y: Some(y),
+            curve: self.curve.clone(),
+        }
+    }
+
+    pub fn double(&self) -> JacobianPoint<F> {
+        // A point with y = 0 doubles to infinity, which the formulas give as Z3 = 2YZ = 0
+        if self.is_identity() {
+            return self.clone();
+        }
+
+        let xx = self.x.clone() * &self.x;
+        let yy = self.y.clone() * &self.y;
+        let zz = self.z.clone() * &self.z;
+
+        // S = 4XY^2, M = 3X^2 + aZ^4
+        let s = self.x.clone() * &yy * 4;
+        let m = xx * 3 + self.curve.a().clone() * &zz * zz;
+
+        // X3 = M^2 - 2S, Y3 = M(S - X3) - 8Y^4, Z3 = 2YZ
+        let x3 = m.clone() * &m - s.clone() * 2;
+        let y3 = m * (s - &x3) - yy.clone() * yy * 8;
+        let z3 = self.y.clone() * &self.z * 2;
+
+        self.with_coordinates(x3, y3, z3)
+    }
+
+    /// Adds an affine point, cheaper than a full addition because its Z is one
+    pub fn add_mixed(&self, other: &Point<F>) -> JacobianPoint<F> {
+        self.assert_same_curve(&other.curve);
+
+        match (&other.x, &other.y) {
+            (Some(x), Some(y)) => self.add_affine(x, y),
+            _ => self.clone(),
+        }
+    }
+
+    /// Adds the affine point (x2, y2) of the same curve
+    pub(crate) fn add_affine(&self, x2: &F, y2: &F) -> JacobianPoint<F> {
+        if self.is_identity() {
+            return self.with_coordinates(x2.clone(), y2.clone(), x2.one());
+        }
+
+        // Bring the affine point to the Z of self: U2 = x2 Z1^2, S2 = y2 Z1^3
+        let z1z1 = self.z.clone() * &self.z;
+        let u2 = x2.clone() * &z1z1;
+        let s2 = y2.clone() * &self.z * z1z1;
+
+        self.add_scaled(&self.x, &self.y, u2, s2, None)
+    }
+
+    /// Checks the two points lie on the same curve, the same assertion as the affine addition
+    fn assert_same_curve(&self, curve: &Arc<Curve<F>>) {
+        assert!(Curve::is_same(&self.curve, curve), "Points are not on the same curve");
+    }
+
+    /// The sum of (U1, S1) and (U2, S2), both points brought to the same Z. `other_z` is the
+    /// Z of the second point, None when it was affine.
+    fn add_scaled(&self, u1: &F, s1: &F, u2: F, s2: F, other_z: Option<&F>) -> JacobianPoint<F> {
+        let h = u2 - u1;
+        let r = s2 - s1;
+
+        if h.is_zero() {
+            // Same x: either the same point or its negation
+            return if r.is_zero() {
+                self.double()
+            } else {
+                JacobianPoint::identity(&self.curve)
+            };
+        }
+
+        let hh = h.clone() * &h;
+        let hhh = h.clone() * &hh;
+        let v = u1.clone() * hh;
+
+        // X3 = R^2 - H^3 - 2V, Y3 = R(V - X3) - S1 H^3, Z3 = Z1 Z2 H
+        let x3 = r.clone() * &r - &hhh - v.clone() * 2;
+        let y3 = r * (v - &x3) - s1.clone() * hhh;
+        let z3 = match other_z {
+            Some(z2) => self.z.clone() * z2 * h,
+            None => self.z.clone() * h,
+        };
+
+        self.with_coordinates(x3, y3, z3)
+    }
+
+    fn with_coordinates(&self, x: F, y: F, z: F) -> JacobianPoint<F> {
+        JacobianPoint {
+            x,
+            y,
+            z,
+            curve: self.curve.clone(),
+        }
+    }
+}
+
+impl<F: Field> From<&Point<F>> for JacobianPoint<F> {
+    fn from(point: &Point<F>) -> JacobianPoint<F> {
+        match (&point.x, &point.y) {
+            (Some(x), Some(y)) => JacobianPoint {
+                x: x.clone(),
+                y: y.clone(),
+                z: x.one(),
+                curve: point.curve.clone(),
+            },
+            _ => JacobianPoint::identity(&point.curve),
+        }
+    }
+}
+
+impl<F: Field> From<JacobianPoint<F>> for Point<F> {
+    fn from(point: JacobianPoint<F>) -> Point<F> {
+        point.to_affine()
+    }
+}
+
+impl<F: Field> PartialEq for JacobianPoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        if !Curve::is_same(&self.curve, &other.curve) {
+            return false;
+        }
+        if self.is_identity() || other.is_identity() {
+            return self.is_identity() && other.is_identity();
+        }
+
+        // X1 Z2^2 = X2 Z1^2 and Y1 Z2^3 = Y2 Z1^3
+        let z1z1 = self.z.clone() * &self.z;
+        let z2z2 = other.z.clone() * &other.z;
+
+        self.x.clone() * &z2z2 == other.x.clone() * &z1z1
+            && self.y.clone() * &other.z * z2z2 == other.y.clone() * &self.z * z1z1
+    }
+}
+
+impl<F: Field> Eq for JacobianPoint<F> {}
+
+impl<F: Field> Add<&JacobianPoint<F>> for &JacobianPoint<F> {
+    type Output = JacobianPoint<F>;
+
+    fn add(self, other: &JacobianPoint<F>) -> JacobianPoint<F> {
+        self.assert_same_curve(&other.curve);
+
+        if self.is_identity() {
+            return other.clone();
+        }
+        if other.is_identity() {
+            return self.clone();
+        }
+
+        // U1 = X1 Z2^2, U2 = X2 Z1^2, S1 = Y1 Z2^3, S2 = Y2 Z1^3
+        let z1z1 = self.z.clone() * &self.z;
+        let z2z2 = other.z.clone() * &other.z;
+        let u1 = self.x.clone() * &z2z2;
+        let u2 = other.x.clone() * &z1z1;
+        let s1 = self.y.clone() * &other.z * z2z2;
+        let s2 = other.y.clone() * &self.z * z1z1;
+
+        self.add_scaled(&u1, &s1, u2, s2, Some(&other.z))
+    }
+}
+
+impl<F: Field> Add for JacobianPoint<F> {
+    type Output = JacobianPoint<F>;
+
+    fn add(self, other: JacobianPoint<F>) -> JacobianPoint<F> {
+        &self + &other
+    }
+}
+
+impl<F: Field> Neg for &JacobianPoint<F> {
+    type Output = JacobianPoint<F>;
+
+    fn neg(self) -> JacobianPoint<F> {
+        self.with_coordinates(self.x.clone(), -self.y.clone(), self.z.clone())
+    }
+}
+
+impl<F: Field> Add<&Point<F>> for &JacobianPoint<F> {
+    type Output = JacobianPoint<F>;
+
+    fn add(self, other: &Point<F>) -> JacobianPoint<F> {
+        self.add_mixed(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::FieldElement;
+    use rug::Integer;
+
+    use crate::{s256_field::secp_generator_point, Point};
+
+    use super::JacobianPoint;
+
+    fn toy_generator() -> Point {
+        let element = |num: u32| FieldElement::new(Integer::from(num), Integer::from(223));
+        Point::new(Some(element(47)), Some(element(71)), element(0), element(7))
+    }
+
+    #[test]
+    fn test_matches_affine() {
+        // Every multiple of (47, 71), the group it generates has order 21
+        let generator = toy_generator();
+        let mut affine = generator.identity();
+        let mut jacobian = JacobianPoint::from(&generator.identity());
+
+        for _ in 0..25 {
+            assert_eq!(jacobian.to_affine(), affine);
+            assert_eq!(jacobian.double().to_affine(), &affine + &affine);
+            assert_eq!((&jacobian + &jacobian).to_affine(), &affine + &affine);
+
+            affine = &affine + &generator;
+            jacobian = &jacobian + &generator;
+        }
+        assert!(JacobianPoint::from(&generator.scalar_mul(Integer::from(21))).is_identity());
+    }
+
+    #[test]
+    fn test_full_and_mixed_addition() {
+        let generator = secp_generator_point();
+        let two = JacobianPoint::from(&generator).double();
+        let three = &two + &generator;
+
+        // The same sum with both points away from Z = 1
+        let five = &two + &three;
+        assert_eq!(five, &three + &two);
+        assert_eq!(five.to_affine(), generator.scalar_mul(Integer::from(5)));
+        assert_eq!(JacobianPoint::from(&five.to_affine()), five);
+
+        // P + (-P) and P + P through the full formula
+        let negative = generator.curve.point(generator.x.clone().unwrap(), -generator.y.clone().unwrap());
+        assert!((&three + &JacobianPoint::from(&negative).double().add_mixed(&negative)).is_identity());
+        assert_eq!(&three + &three, three.double());
+    }
+}
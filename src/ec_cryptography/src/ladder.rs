@@ -0,0 +1,150 @@
+// Constant-time scalar multiplication for secret scalars.
+//
+// `scalar_mul` skips zero digits and its additions branch on the points, so its timing
+// depends on the scalar. Multiplying by a `Scalar`, the type of private keys and nonces, uses
+// a Montgomery ladder instead: always 256 steps of one addition and one doubling, with the
+// bit choosing which registers to swap through masks rather than a branch. The group law is
+// the complete addition of Renes, Costello and Batina for a = 0 curves in projective
+// coordinates (x = X/Z, y = Y/Z), which has no special case for doubling or the identity, and
+// all the field operations are the `ct_*` ones of Fe256.
+
+use std::ops::Mul;
+
+use finite_fields::{Choice, ConstantTime, Fe256, Scalar};
+
+use crate::Point;
+
+/// A point in projective coordinates, the identity is (0 : 1 : 0)
+#[derive(Clone, Copy)]
+struct ProjectivePoint {
+    x: Fe256,
+    y: Fe256,
+    z: Fe256,
+}
+
+impl ProjectivePoint {
+    const IDENTITY: ProjectivePoint = ProjectivePoint { x: Fe256::ZERO, y: Fe256::ONE, z: Fe256::ZERO };
+
+    /// self + other on y^2 = x^3 + b, for any two points. `b3` is 3b.
+    fn add(&self, other: &ProjectivePoint, b3: &Fe256) -> ProjectivePoint {
+        let xx = self.x.ct_mul(&other.x);
+        let yy = self.y.ct_mul(&other.y);
+        let zz = self.z.ct_mul(&other.z);
+
+        // X1 Y2 + X2 Y1, Y1 Z2 + Y2 Z1 and X1 Z2 + X2 Z1
+        let cross = |a1: &Fe256, b1: &Fe256, a2: &Fe256, b2: &Fe256, aa: &Fe256, bb: &Fe256| {
+            a1.ct_add(b1).ct_mul(&a2.ct_add(b2)).ct_sub(&aa.ct_add(bb))
+        };
+        let xy = cross(&self.x, &self.y, &other.x, &other.y, &xx, &yy);
+        let yz = cross(&self.y, &self.z, &other.y, &other.z, &yy, &zz);
+        let xz = cross(&self.x, &self.z, &other.x, &other.z, &xx, &zz);
+
+        let b3zz = b3.ct_mul(&zz);
+        let yy_minus = yy.ct_sub(&b3zz);
+        let yy_plus = yy.ct_add(&b3zz);
+        let xx3 = xx.ct_add(&xx).ct_add(&xx);
+
+        // X3 = XY (YY - 3b ZZ) - 3b YZ XZ
+        // Y3 = (YY + 3b ZZ)(YY - 3b ZZ) + 9b XX XZ
+        // Z3 = YZ (YY + 3b ZZ) + 3 XX XY
+        ProjectivePoint {
+            x: xy.ct_mul(&yy_minus).ct_sub(&b3.ct_mul(&yz).ct_mul(&xz)),
+            y: yy_plus.ct_mul(&yy_minus).ct_add(&b3.ct_mul(&xx3).ct_mul(&xz)),
+            z: yz.ct_mul(&yy_plus).ct_add(&xx3.ct_mul(&xy)),
+        }
+    }
+
+    fn swap(a: &mut ProjectivePoint, b: &mut ProjectivePoint, choice: Choice) {
+        Fe256::ct_swap(&mut a.x, &mut b.x, choice);
+        Fe256::ct_swap(&mut a.y, &mut b.y, choice);
+        Fe256::ct_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+impl Point<Fe256> {
+    /// scalar * self in constant time, see the module comment. Only for curves with a = 0,
+    /// such as secp256k1.
+    pub fn ct_scalar_mul(&self, scalar: &Scalar) -> Point<Fe256> {
+        assert!(self.curve.a().is_zero(), "The ladder needs a curve with a = 0");
+
+        // The point itself is public
+        let point = match (self.x, self.y) {
+            (Some(x), Some(y)) => ProjectivePoint { x, y, z: Fe256::ONE },
+            _ => return self.identity(),
+        };
+        let b3 = self.curve.b().ct_mul(&Fe256::from_u64(3));
+        let bytes = scalar.to_be_bytes();
+
+        // Invariant: r1 = r0 + P
+        let (mut r0, mut r1) = (ProjectivePoint::IDENTITY, point);
+        for i in (0..256).rev() {
+            let bit = Choice::from_bit((bytes[31 - i / 8] >> (i % 8)) as u64);
+
+            ProjectivePoint::swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1, &b3);
+            r0 = r0.add(&r0, &b3);
+            ProjectivePoint::swap(&mut r0, &mut r1, bit);
+        }
+
+        // Whether the result is the identity is public, like the result itself
+        if r0.z.ct_is_zero().declassify() {
+            return self.identity();
+        }
+        let z_inverse = r0.z.ct_invert();
+        self.with_coordinates(r0.x.ct_mul(&z_inverse), r0.y.ct_mul(&z_inverse))
+    }
+}
+
+/// Multiplication by a secret scalar always takes the constant-time ladder
+impl Mul<&Scalar> for &Point<Fe256> {
+    type Output = Point<Fe256>;
+
+    fn mul(self, scalar: &Scalar) -> Point<Fe256> {
+        self.ct_scalar_mul(scalar)
+    }
+}
+
+/// The same for a secp256k1 point over FieldElement, computed on the fixed-width field.
+/// Panics if the point is not over the secp256k1 field.
+impl Mul<&Scalar> for &Point {
+    type Output = Point;
+
+    fn mul(self, scalar: &Scalar) -> Point {
+        let point = Point::<Fe256>::try_from(self).expect("Secret scalars multiply secp256k1 points");
+        point.ct_scalar_mul(scalar).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::{rng, Fe256, Scalar};
+    use rug::Integer;
+
+    use crate::{s256_field::secp_generator_point, Point};
+
+    #[test]
+    fn test_matches_variable_time() {
+        let mut rng = rng::deterministic(9);
+        let generator = Point::<Fe256>::try_from(&secp_generator_point()).unwrap();
+        let point = generator.scalar_mul(Integer::from(5000));
+
+        let mut scalars = vec![Scalar::ZERO, Scalar::ONE, Scalar::from_u64(2), -Scalar::ONE];
+        scalars.extend((0..10).map(|_| Scalar::random(&mut rng)));
+        for scalar in scalars {
+            assert_eq!(&generator * &scalar, generator.scalar_mul(scalar.to_integer()));
+            assert_eq!(&point * &scalar, point.scalar_mul(scalar.to_integer()));
+        }
+
+        // n - 1 times P is -P, and the identity stays the identity
+        assert_eq!(&point * &-Scalar::ONE, -&point);
+        assert_eq!(&point.identity() * &Scalar::from_u64(7), point.identity());
+    }
+
+    #[test]
+    fn test_field_element_points() {
+        let generator = secp_generator_point();
+        let scalar = Scalar::from_integer(&Integer::from_str_radix("deadbeef12345", 16).unwrap());
+
+        assert_eq!(&generator * &scalar, generator.scalar_mul(scalar.to_integer()));
+    }
+}
@@ -1,127 +1,202 @@
-use std::{fmt::Debug, ops::Add};
-use rug::{integer::Order, ops::{Pow, RemRounding}, Integer};
+use std::{fmt::Debug, ops::{Add, Neg}, sync::Arc};
+use rug::{ops::RemRounding, Integer};
 
-mod s256_field;
+pub mod s256_field;
 pub mod traits;
 
+pub mod counting;
+pub mod curve;
+pub mod dlog;
+pub mod edwards;
+pub mod error;
 pub mod helper;
+pub mod jacobian;
+pub mod ladder;
+pub mod montgomery;
+pub mod multiplication;
+pub mod private_key;
+pub mod rfc6979;
 pub mod serializer;
 
-use traits::Serializer;
-use finite_fields::FieldElement;
-
+pub use curve::{Curve, SECP256K1, SECP256R1};
+pub use dlog::{dlog_bsgs, dlog_pohlig_hellman, dlog_pollard_rho};
+pub use edwards::{EdwardsCurve, EdwardsPoint};
+pub use error::{CurveError, DerError};
+pub use jacobian::JacobianPoint;
+pub use montgomery::{MontgomeryCurve, MontgomeryPoint};
+pub use multiplication::multi_scalar_mul;
+pub use private_key::PrivateKey;
+
+use curve::SECP256K1_FIXED_WIDTH;
+use finite_fields::{Fe256, Field, FieldElement, FieldError};
+use s256_field::secp256k1_field;
+
+/// A point on the curve y^2 = x^3 + ax + b over the field F: FieldElement or Fe256 for
+/// cryptography, Rational for the teaching curves of the `elliptic_curve` crate.
+/// None coordinates are the point at infinity.
 #[derive(Clone)]
-pub struct EllipticCurve {
-    x: Option<FieldElement>,
-    y: Option<FieldElement>,
-    // a and b are constants of the EC
-    a: FieldElement,
-    b: FieldElement,
+pub struct Point<F = FieldElement> {
+    x: Option<F>,
+    y: Option<F>,
+    // The curve the point is on, shared by all its points
+    curve: Arc<Curve<F>>,
 }
 
-impl Debug for EllipticCurve {
+/// The former name of `Point`
+pub type EllipticCurve<F = FieldElement> = Point<F>;
+
+impl<F: Field> Debug for Point<F> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f, 
-            "EllipticCurve({:?}, {:?})", 
-            self.x.clone().unwrap_or_else(|| FieldElement::new(Integer::ZERO, Integer::from(3))), 
-            self.y.clone().unwrap_or(FieldElement::new(Integer::ZERO, Integer::from(3)))
-        )
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => write!(f, "Point({:?}, {:?})", x, y),
+            _ => write!(f, "Point(infinity)"),
+        }
     }
 }
 
-impl EllipticCurve {
+impl<F: Field> Point<F> {
+    /// The point (x, y) on a new curve with the given a and b, or the point at infinity if a
+    /// coordinate is None. Use `Curve::point` to put several points on one shared curve.
     pub fn new(
-        x: Option<FieldElement>,
-        y: Option<FieldElement>,
-        a: FieldElement,
-        b: FieldElement,
-    ) -> EllipticCurve {
-        if x.is_none() && y.is_none() {
-            return EllipticCurve { x: None, y: None, a, b };
+        x: Option<F>,
+        y: Option<F>,
+        a: F,
+        b: F,
+    ) -> Point<F> {
+        Point::on_curve(&Curve::new(a, b), x, y)
+    }
+
+    pub(crate) fn on_curve(curve: &Arc<Curve<F>>, x: Option<F>, y: Option<F>) -> Point<F> {
+        if x.is_none() || y.is_none() {
+            return Point { x: None, y: None, curve: curve.clone() };
         }
 
-        let point = EllipticCurve { x, y, a, b };
+        let point = Point { x, y, curve: curve.clone() };
         // Ensure that the point is on the curve
         assert!(point.is_valid(), "Point is not on the curve");
-    
+
         point
     }
 
-    pub fn prime(&self) -> Integer {
-        self.a.order()
+    pub fn x(&self) -> Option<&F> {
+        self.x.as_ref()
+    }
+
+    pub fn y(&self) -> Option<&F> {
+        self.y.as_ref()
+    }
+
+    pub fn curve(&self) -> &Arc<Curve<F>> {
+        &self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_none() || self.y.is_none()
     }
 
     pub fn is_valid(&self) -> bool {
-        if self.x.is_none() || self.y.is_none() {
-            return true;
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => self.curve.contains(x, y),
+            _ => true,
         }
-        self.y.clone().unwrap().pow(Integer::from(2)).unwrap() == 
-            (self.x.clone().unwrap().pow(Integer::from(3))).unwrap() + (self.a.clone() * self.x.clone().unwrap()) + self.b.clone()
     }
 
-    pub fn slope(&self, other: EllipticCurve) -> Option<FieldElement> {
+    pub fn slope(&self, other: &Point<F>) -> Option<F> {
         // Implement the slope of the curve
-        if self.x.is_none() && other.x.is_none() {
-            return None;
-        }
-
-        let numerator = (other.y.unwrap().num() - self.y.clone().unwrap().num()).rem_euc(self.prime());
-        let denominator = (other.x.unwrap().num() - self.x.clone().unwrap().num()).rem_euc(self.prime());
+        let (x1, y1) = (self.x.as_ref()?, self.y.as_ref()?);
+        let (x2, y2) = (other.x.as_ref()?, other.y.as_ref()?);
 
-        let slope = FieldElement::new(numerator, self.prime()) / FieldElement::new(denominator, self.prime());
-        Some(slope)
+        // (y2 - y1) / (x2 - x1), a vertical line has no slope
+        Some((y2.clone() - y1) * (x2.clone() - x1).inv()?)
     }
 
-    pub fn tangent_slope(&self) -> Option<FieldElement> {
+    pub fn tangent_slope(&self) -> Option<F> {
         // Implement the slope of the tangent line
-        if self.x.is_none() {
-            return None;
-        }
-
-        let x_pow = self.x.clone().unwrap().num().pow(2).rem_euc(self.prime());
-        let numerator: Integer = ((Integer::from(3) * x_pow).rem_euc(self.prime()) + self.a.clone().num()).rem_euc(self.prime());
-        let denominator: Integer = (Integer::from(2) * self.y.clone().unwrap().num()).rem_euc(self.prime());
+        let (x, y) = (self.x.as_ref()?, self.y.as_ref()?);
 
-        let slope = FieldElement::new(numerator, self.prime()) / FieldElement::new(denominator, self.prime());
-        Some(slope)
+        // (3x^2 + a) / 2y
+        Some((x.clone() * x * 3 + self.curve.a()) * (y.clone() * 2).inv()?)
     }
 
     pub fn identity(&self) -> Self {
-        EllipticCurve::new(
-            None,
-            None,
-            self.a.clone(),
-            self.b.clone()
-        )
+        self.curve.identity()
     }
 
-    pub fn scalar_mul(&self, coefficient: Integer) -> EllipticCurve {
-        let mut current = self.clone();
-        let mut result = EllipticCurve::new(None, None, self.a.clone(), self.b.clone());
-        let mut scalar = coefficient;
-
-        while scalar > Integer::ZERO {
-            if (&scalar & Integer::from(1)) == Integer::from(1) {
-                result = result + current.clone();
-            }
+    /// coefficient * self, a negative coefficient multiplies -self. The generator of a curve
+    /// uses the curve's comb table, other points a wNAF, see the `multiplication` module.
+    /// For the points of `SECP256K1`, `secp256k1_scalar_mul` is faster.
+    pub fn scalar_mul(&self, coefficient: Integer) -> Point<F> {
+        if self.curve.is_generator(self) {
+            let order = self.curve.generator_order().unwrap();
+            return self.curve.generator_table().mul(&self.curve, &coefficient.rem_euc(order));
+        }
 
-            current = current.clone() + current.clone();
+        multi_scalar_mul(&[(&coefficient, self)])
+    }
 
-            scalar >>= 1;
+    /// Builds the point (x, y) on the same curve as self, without checking it is on the curve.
+    /// Only used for the results of the group law, which are always on the curve.
+    fn with_coordinates(&self, x: F, y: F) -> Point<F> {
+        Point {
+            x: Some(x),
+            y: Some(y),
+            curve: self.curve.clone(),
         }
-        result
     }
+}
 
-    pub fn secp_point(x: Integer, y: Integer) -> EllipticCurve {
-        let prime = Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977);
-        
-        EllipticCurve::new(
-            Some(FieldElement::new(x, prime.clone())),
-            Some(FieldElement::new(y, prime.clone())),
-            FieldElement::new(Integer::ZERO, prime.clone()),
-            FieldElement::new(Integer::from(7), prime.clone())
-        )
+impl Point {
+    pub fn prime(&self) -> Integer {
+        self.curve.prime()
+    }
+
+    /// coefficient * self for a point of secp256k1, computed on the fixed-width Fe256
+    /// arithmetic and converted back. Panics if the point is not over the secp256k1 field.
+    pub fn secp256k1_scalar_mul(&self, coefficient: Integer) -> Point {
+        Point::<Fe256>::try_from(self).unwrap().scalar_mul(coefficient).into()
+    }
+
+    pub fn secp_point(x: Integer, y: Integer) -> Point {
+        let field = secp256k1_field();
+        SECP256K1.point(field.element(x), field.element(y))
+    }
+}
+
+/// Moves a point over the secp256k1 field to the fixed-width Fe256 arithmetic
+impl TryFrom<&Point> for Point<Fe256> {
+    type Error = FieldError;
+
+    fn try_from(point: &Point) -> Result<Point<Fe256>, FieldError> {
+        let coordinate = |element: &Option<FieldElement>| element.as_ref().map(Fe256::try_from).transpose();
+
+        // Points of SECP256K1 all share one converted curve
+        let curve = if Arc::ptr_eq(&point.curve, &SECP256K1) {
+            SECP256K1_FIXED_WIDTH.clone()
+        } else {
+            Arc::new(Curve::try_from(&*point.curve)?)
+        };
+
+        Ok(Point {
+            x: coordinate(&point.x)?,
+            y: coordinate(&point.y)?,
+            curve,
+        })
+    }
+}
+
+impl From<Point<Fe256>> for Point {
+    fn from(point: Point<Fe256>) -> Point {
+        let curve = if Arc::ptr_eq(&point.curve, &SECP256K1_FIXED_WIDTH) {
+            SECP256K1.clone()
+        } else {
+            Arc::new(Curve::from(&*point.curve))
+        };
+
+        Point {
+            x: point.x.map(FieldElement::from),
+            y: point.y.map(FieldElement::from),
+            curve,
+        }
     }
 }
 
@@ -135,91 +210,87 @@ pub fn reverse_bits(number: Integer) -> String {
     reversed
 }
 
-impl Eq for EllipticCurve {}
+impl<F: Field> Eq for Point<F> {}
 
-impl PartialEq for EllipticCurve {
+impl<F: Field> PartialEq for Point<F> {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x 
             && self.y == other.y 
-            && self.a == other.a 
-            && self.b == other.b
+            && Curve::is_same(&self.curve, &other.curve)
     }
 }
 
-impl Add for EllipticCurve {
-    type Output = EllipticCurve;
+impl<F: Field> Add<&Point<F>> for &Point<F> {
+    type Output = Point<F>;
 
-    fn add(self, other: EllipticCurve) -> EllipticCurve {
-        // Ensure that the 2 Points are on the same curve, i.e. they have the same a and b
-        assert!(self.a == other.a && self.b == other.b, "Points are not on the same curve");
-        
-        // If other is the the Identity Point (or the Point at Infinity)
-        if other.x.is_none() {
-            return self;
-        }
+    fn add(self, other: &Point<F>) -> Point<F> {
+        // Ensure that the 2 Points are on the same curve
+        assert!(Curve::is_same(&self.curve, &other.curve), "Points are not on the same curve");
 
-        // Similarly, if self is the Identity Point, return other
-        if self.x.is_none() {
-            return other;
-        }
+        // If either point is the Identity Point (or the Point at Infinity), the sum is the other one
+        let (x1, y1) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return other.clone(),
+        };
+        let (x2, y2) = match (&other.x, &other.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return self.clone(),
+        };
 
-        // if self.x != other.x
-        if self.x != other.x {
-            // calculate the slope of the line
+        let slope = if x1 != x2 {
             // There will be a definite slope because neither of the point is the Identity Point
-            let slope = self.slope(other.clone()).unwrap();
-
-            let x1 = self.x.clone().unwrap().num();
-            let x2 = other.x.unwrap().num();
-            let y1 = self.y.clone().unwrap().num();
-
-            // calculate the x-coordinate of the third point
-            let x3 = (slope.pow(Integer::from(2)).unwrap().num() - x1.clone() - x2).rem_euc(self.prime());
-            let y3 = ((slope.num() * (x1 - x3.clone())).rem_euc(self.prime()) - y1).rem_euc(self.prime());
-
-            return EllipticCurve::new(
-                Some(FieldElement::new(x3, self.prime())),
-                Some(FieldElement::new(y3, self.prime())),
-                self.a.clone(),
-                self.b.clone()
-            );
-        }
+            self.slope(other).unwrap()
+        } else if y1 == y2 && !y1.is_zero() {
+            // If the points are the same, then we need to find the tangent slope
+            self.tangent_slope().unwrap()
+        } else {
+            // P + (-P), or doubling a point with a vertical tangent
+            return self.identity();
+        };
 
-        // if self == other
-        if self == other {
-            if self.y.clone().unwrap().num() == Integer::from(0) {
-                return self.identity();
-            }
+        // x3 = s^2 - x1 - x2, y3 = s(x1 - x3) - y1
+        let x3 = slope.clone() * &slope - x1 - x2;
+        let y3 = slope * (x1.clone() - &x3) - y1;
 
-            // If the points are the same, then we need to find the tangent slope
-            let slope = self.tangent_slope().unwrap();
-
-            let x1 = self.x.clone().unwrap().num();
-            let y1 = self.y.clone().unwrap().num();
-            
-            let x3 = (slope.num().pow(2).rem_euc(self.prime()) - (Integer::from(2) * x1.clone()).rem_euc(self.prime())).rem_euc(self.prime());
-            let y3 = ((slope.num() * (x1 - x3.clone())).rem_euc(self.prime()) - y1).rem_euc(self.prime());
-
-            return EllipticCurve::new(
-                Some(FieldElement::new(x3, self.prime())),
-                Some(FieldElement::new(y3, self.prime())),
-                self.a.clone(),
-                self.b.clone()
-            );
+        self.with_coordinates(x3, y3)
+    }
+}
+
+impl<F: Field> Neg for &Point<F> {
+    type Output = Point<F>;
+
+    fn neg(self) -> Point<F> {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => self.with_coordinates(x.clone(), -y.clone()),
+            _ => self.identity(),
         }
+    }
+}
+
+impl<F: Field> Neg for Point<F> {
+    type Output = Point<F>;
 
-        self.identity()
+    fn neg(self) -> Point<F> {
+        -&self
+    }
+}
+
+impl<F: Field> Add for Point<F> {
+    type Output = Point<F>;
+
+    fn add(self, other: Point<F>) -> Point<F> {
+        &self + &other
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{panic};
+    use std::{panic, sync::Arc};
 
-    use finite_fields::FieldElement;
-    use rug::{integer::Order, ops::Pow, rand::RandState, Complete, Integer};
+    use finite_fields::{Fe256, FieldElement, Scalar};
+    use rug::{integer::Order, ops::Pow, Complete, Integer, Rational};
 
-    use crate::{helper::double_hash, s256_field::secp_generator_point, EllipticCurve};
+    use crate::{helper::double_hash, s256_field::{secp_generator_point, S256Field}, multi_scalar_mul, Point, PrivateKey, SECP256K1};
 
     #[test]
     fn test_on_curve() {
@@ -240,7 +311,7 @@ mod tests {
         for (x_raw, y_raw) in valid_points.iter() {
             let x = FieldElement::new(x_raw.clone(), prime.clone());
             let y = FieldElement::new(y_raw.clone(), prime.clone());
-            assert_eq!(EllipticCurve::new(Some(x), Some(y), a.clone(), b.clone()).is_valid(), true);
+            assert_eq!(Point::new(Some(x), Some(y), a.clone(), b.clone()).is_valid(), true);
         }
 
         for (x_raw, y_raw) in invalid_points.iter() {
@@ -248,7 +319,7 @@ mod tests {
             let y = FieldElement::new(y_raw.clone(), prime.clone());
 
             let result = panic::catch_unwind(|| {
-                EllipticCurve::new(Some(x), Some(y), a.clone(), b.clone()).is_valid()
+                Point::new(Some(x), Some(y), a.clone(), b.clone()).is_valid()
             });
             assert!(result.is_err(), "Point is not on the curve");
         }
@@ -289,9 +360,9 @@ mod tests {
         ];
 
         for (x1, y1, x2, y2, x3, y3) in points {
-            let point_a = EllipticCurve::new(Some(x1), Some(y1), a.clone(), b.clone());
-            let point_b = EllipticCurve::new(Some(x2), Some(y2), a.clone(), b.clone());
-            let point_3 = point_a.clone() + point_b.clone();
+            let point_a = Point::new(Some(x1), Some(y1), a.clone(), b.clone());
+            let point_b = Point::new(Some(x2), Some(y2), a.clone(), b.clone());
+            let point_3 = &point_a + &point_b;
 
             assert_eq!(point_3.x.unwrap().num(), x3.num());
             assert_eq!(point_3.y.unwrap().num(), y3.num());
@@ -316,7 +387,7 @@ mod tests {
 
         // let point = 
         let result = panic::catch_unwind(|| {
-            EllipticCurve::new(
+            Point::new(
                 Some(x.clone()),
                 Some(y.clone()),
                 a.clone(),
@@ -325,7 +396,7 @@ mod tests {
         });
         assert!(result.is_ok(), "Point is not on the curve");
 
-        // let new_point = EllipticCurve::new(
+        // let new_point = Point::new(
         //     Some(x),
         //     Some(y),
         //     a.clone(),
@@ -336,6 +407,48 @@ mod tests {
         
     }
 
+    #[test]
+    fn test_secp_scalar_mul_order() {
+        let generator = secp_generator_point();
+        let order = S256Field::order();
+
+        assert_eq!(generator.scalar_mul(order.clone()), generator.identity());
+        assert_eq!(generator.scalar_mul(order.clone() + 1), generator);
+        assert_eq!(
+            generator.scalar_mul(order - 1),
+            generator.scalar_mul(Integer::from(2)) + generator.scalar_mul(Integer::from(-3) + S256Field::order())
+        );
+    }
+
+    #[test]
+    fn test_generic_fields() {
+        // The same secp256k1 point over the fixed-width field
+        let generator = secp_generator_point();
+        let fixed_width = Point::<Fe256>::try_from(&generator).unwrap();
+        let multiple = fixed_width.scalar_mul(Integer::from(5000));
+
+        assert_eq!(Point::from(multiple), generator.scalar_mul(Integer::from(5000)));
+        assert_eq!(fixed_width.scalar_mul(S256Field::order()), fixed_width.identity());
+
+        // secp256k1_scalar_mul runs on Fe256 and comes back on the shared curve
+        let point = generator.scalar_mul(Integer::from(5000));
+        let product = point.secp256k1_scalar_mul(Integer::from(-77));
+        assert_eq!(product, multi_scalar_mul(&[(&Integer::from(-77), &point)]));
+        assert!(Arc::ptr_eq(product.curve(), &SECP256K1));
+
+        // y^2 = x^3 + 5x + 7 over the rationals
+        let point = |x: i32, y: i32| Point::new(
+            Some(Rational::from(x)),
+            Some(Rational::from(y)),
+            Rational::from(5),
+            Rational::from(7)
+        );
+
+        assert_eq!(point(2, 5) + point(-1, -1), point(3, -7));
+        assert_eq!(point(-1, -1) + point(-1, -1), point(18, 77));
+        assert_eq!(point(-1, -1) + point(-1, 1), point(-1, 1).identity());
+    }
+
     #[test]
     fn test_secp_signature_verfication() {
         /*
@@ -356,7 +469,7 @@ mod tests {
         let point_x = Integer::parse_radix("887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c", 16).unwrap().complete();
         let point_y = Integer::from_str_radix("61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34", 16).unwrap();
 
-        let point = EllipticCurve::secp_point(
+        let point = Point::secp_point(
             point_x, 
             point_y
         );
@@ -375,8 +488,8 @@ mod tests {
         );
         let s = FieldElement::new(Integer::parse_radix("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6", 16).unwrap().complete(), order.clone());
 
-        let u = z / s.clone();
-        let v = r.clone() / s;
+        let u = z / &s;
+        let v = &r / s;
         
         let u_point = generator_point.scalar_mul(u.num());
         let v_point = point.scalar_mul(v.num());
@@ -391,24 +504,20 @@ mod tests {
         // To implement signatures, we must have z, a scalar integer
         // choose a random integer k
         // calculate R = kG and r = R.x
-        // calculate s = (z + re)/k
+        // calculate s = (z + re)/k, with the inverse of k mod n
         // The signature is (r, s)
 
-        // let's generate a random k
-        let random_int = Integer::from(RandState::new_mersenne_twister().bits(32));
-        
-        let secret_hash = double_hash("my message");
-        let secret = Integer::from_digits(&secret_hash, Order::Msf);
-        
+        let secret_hash = double_hash("my secret");
+        let key = PrivateKey::new(Scalar::from_integer(&Integer::from_digits(&secret_hash, Order::Msf)));
+
         let message_hash = double_hash("my message");
         let message = Integer::from_digits(&message_hash, Order::Msf);
-       
-       
-        let generator_point = secp_generator_point();
-        let signature_point = generator_point.scalar_mul(random_int.clone());
-        let s = (message + (secret.clone() * signature_point.x.unwrap().num())) / random_int;
 
-        let point = secp_generator_point().scalar_mul(secret);
-        println!("The signed point is {:?}", point);
+        // PrivateKey picks the nonce, and keeps s below n / 2
+        let signature = key.sign(message.clone());
+        assert!(!Scalar::from_integer(signature.s()).is_high());
+
+        // uG + vP has r as its x coordinate, with u = z/s and v = r/s
+        assert!(key.public_key().verify(message, signature));
     }
 }
\ No newline at end of file
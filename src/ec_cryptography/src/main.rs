@@ -1,4 +1,4 @@
-use ec_cryptography::{EllipticCurve, reverse_bits, traits::Serializer};
+use ec_cryptography::{Point, reverse_bits, traits::Serializer};
 use finite_fields::FieldElement;
 use rug::Integer;
 use rug::{ops::Pow, Complete};
@@ -9,12 +9,12 @@ fn main() {
     // let a = FieldElement::new(Integer::from(0), prime.clone());
     // let b = FieldElement::new(Integer::from(7), prime.clone());
 
-    // let point = EllipticCurve::new(
+    // let point = Point::new(
     //     Some(FieldElement::new(Integer::from(47), prime.clone())),
     //     Some(FieldElement::new(Integer::from(71), prime.clone())),
     //     a.clone(), b.clone()
     // );
-    // let pointb = EllipticCurve::new(
+    // let pointb = Point::new(
     //     Some(FieldElement::new(Integer::from(36), prime.clone())),
     //     Some(FieldElement::new(Integer::from(111), prime.clone())),
     //     a, b
@@ -46,7 +46,7 @@ fn main() {
     let y = FieldElement::new(gy, prime.clone());
 
     // let point = 
-    let _generator = EllipticCurve::new(
+    let _generator = Point::new(
         Some(x.clone()),
         Some(y.clone()),
         a.clone(),
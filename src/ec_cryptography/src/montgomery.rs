@@ -0,0 +1,243 @@
+// Curves in Montgomery form By^2 = x^3 + Ax^2 + x, the model of Curve25519.
+//
+// Every Montgomery curve is isomorphic to a short Weierstrass curve: x = Bu - A/3, y = Bv
+// sends the point (u, v) of y^2 = x^3 + ax + b, with a = (3 - A^2) / 3B^2 and
+// b = (2A^3 - 9A) / 27B^3, to the Montgomery point (x, y). The map is a group isomorphism, so
+// the addition law below gives the same sums as the one of `Point` on the other side. Each
+// `MontgomeryCurve` keeps its Weierstrass curve, which lets converted points share it.
+
+use std::{fmt::Debug, ops::{Add, Neg}, sync::Arc};
+
+use finite_fields::FieldElement;
+use rug::Integer;
+
+use crate::{helper::{double_and_add, is_same_curve}, Curve, Point};
+
+/// A curve By^2 = x^3 + Ax^2 + x over a prime field with p > 3
+#[derive(Clone, Debug, PartialEq)]
+pub struct MontgomeryCurve {
+    a: FieldElement,
+    b: FieldElement,
+    // The isomorphic short Weierstrass curve
+    weierstrass: Arc<Curve>,
+}
+
+impl MontgomeryCurve {
+    /// Panics if the curve is singular, B(A^2 - 4) = 0
+    pub fn new(a: FieldElement, b: FieldElement) -> Arc<MontgomeryCurve> {
+        assert!(a.order() > 3, "Montgomery curves need a field with p > 3");
+        assert!(!(b.clone() * (a.clone() * &a - 4u64)).is_zero(), "The curve is singular");
+
+        let three_b = b.clone() * 3u64;
+        let weierstrass = Curve::new(
+            (-(a.clone() * &a) + 3u64) / (three_b.clone() * &b),
+            (a.clone() * &a * &a * 2u64 - a.clone() * 9u64) / (three_b.clone() * &three_b * &three_b),
+        );
+        Arc::new(MontgomeryCurve { a, b, weierstrass })
+    }
+
+    pub fn a(&self) -> &FieldElement {
+        &self.a
+    }
+
+    pub fn b(&self) -> &FieldElement {
+        &self.b
+    }
+
+    /// The short Weierstrass curve the points convert to
+    pub fn weierstrass(&self) -> &Arc<Curve> {
+        &self.weierstrass
+    }
+
+    /// True if (x, y) satisfies By^2 = x^3 + Ax^2 + x
+    pub fn contains(&self, x: &FieldElement, y: &FieldElement) -> bool {
+        self.b.clone() * y * y == x.clone() * x * x + self.a.clone() * x * x + x
+    }
+
+    /// The point (x, y), panics if it is not on the curve
+    pub fn point(self: &Arc<Self>, x: FieldElement, y: FieldElement) -> MontgomeryPoint {
+        assert!(self.contains(&x, &y), "Point is not on the curve");
+        MontgomeryPoint { coordinates: Some((x, y)), curve: self.clone() }
+    }
+
+    /// The point at infinity
+    pub fn identity(self: &Arc<Self>) -> MontgomeryPoint {
+        MontgomeryPoint { coordinates: None, curve: self.clone() }
+    }
+
+    pub(crate) fn is_same(this: &Arc<Self>, other: &Arc<Self>) -> bool {
+        is_same_curve(this, other)
+    }
+}
+
+/// A point on a Montgomery curve, None is the point at infinity
+#[derive(Clone)]
+pub struct MontgomeryPoint {
+    coordinates: Option<(FieldElement, FieldElement)>,
+    curve: Arc<MontgomeryCurve>,
+}
+
+impl Debug for MontgomeryPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.coordinates {
+            Some((x, y)) => write!(f, "MontgomeryPoint({:?}, {:?})", x, y),
+            None => write!(f, "MontgomeryPoint(infinity)"),
+        }
+    }
+}
+
+impl MontgomeryPoint {
+    pub fn x(&self) -> Option<&FieldElement> {
+        self.coordinates.as_ref().map(|(x, _)| x)
+    }
+
+    pub fn y(&self) -> Option<&FieldElement> {
+        self.coordinates.as_ref().map(|(_, y)| y)
+    }
+
+    pub fn curve(&self) -> &Arc<MontgomeryCurve> {
+        &self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.coordinates.is_none()
+    }
+
+    /// The same point on the short Weierstrass curve, (x / B + A / 3B, y / B)
+    pub fn to_weierstrass(&self) -> Point {
+        let curve = &self.curve.weierstrass;
+        match &self.coordinates {
+            Some((x, y)) => {
+                let shift = self.curve.a.clone() / (self.curve.b.clone() * 3u64);
+                curve.point(x.clone() / &self.curve.b + shift, y.clone() / &self.curve.b)
+            }
+            None => curve.identity(),
+        }
+    }
+
+    /// The point of `curve` that converts to `point`, which must be on `curve.weierstrass()`
+    pub fn from_weierstrass(curve: &Arc<MontgomeryCurve>, point: &Point) -> MontgomeryPoint {
+        assert!(Curve::is_same(point.curve(), &curve.weierstrass), "Point is not on the Weierstrass form of the curve");
+
+        match (point.x(), point.y()) {
+            (Some(u), Some(v)) => {
+                let x = curve.b.clone() * u - curve.a.clone() / curve.a.field().element(Integer::from(3));
+                curve.point(x, curve.b.clone() * v)
+            }
+            _ => curve.identity(),
+        }
+    }
+
+    /// scalar * self, by double-and-add
+    pub fn scalar_mul(&self, scalar: Integer) -> MontgomeryPoint {
+        double_and_add(self, scalar, self.curve.identity())
+    }
+}
+
+impl PartialEq for MontgomeryPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.coordinates == other.coordinates && MontgomeryCurve::is_same(&self.curve, &other.curve)
+    }
+}
+
+impl Eq for MontgomeryPoint {}
+
+impl Add<&MontgomeryPoint> for &MontgomeryPoint {
+    type Output = MontgomeryPoint;
+
+    fn add(self, other: &MontgomeryPoint) -> MontgomeryPoint {
+        assert!(MontgomeryCurve::is_same(&self.curve, &other.curve), "Points are not on the same curve");
+
+        let ((x1, y1), (x2, y2)) = match (&self.coordinates, &other.coordinates) {
+            (Some(first), Some(second)) => (first, second),
+            (None, _) => return other.clone(),
+            (_, None) => return self.clone(),
+        };
+        let (a, b) = (&self.curve.a, &self.curve.b);
+
+        let slope = if x1 != x2 {
+            (y2.clone() - y1) / (x2.clone() - x1)
+        } else if y1 == y2 && !y1.is_zero() {
+            // The tangent: (3x^2 + 2Ax + 1) / 2By
+            (x1.clone() * x1 * 3u64 + a.clone() * x1 * 2u64 + 1u64) / (b.clone() * y1 * 2u64)
+        } else {
+            // P + (-P), or doubling a point of order 2
+            return self.curve.identity();
+        };
+
+        // x3 = B s^2 - A - x1 - x2, y3 = s(x1 - x3) - y1
+        let x3 = b.clone() * &slope * &slope - a - x1 - x2;
+        let y3 = slope * (x1.clone() - &x3) - y1;
+        MontgomeryPoint { coordinates: Some((x3, y3)), curve: self.curve.clone() }
+    }
+}
+
+impl Add for MontgomeryPoint {
+    type Output = MontgomeryPoint;
+
+    fn add(self, other: MontgomeryPoint) -> MontgomeryPoint {
+        &self + &other
+    }
+}
+
+impl Neg for &MontgomeryPoint {
+    type Output = MontgomeryPoint;
+
+    fn neg(self) -> MontgomeryPoint {
+        MontgomeryPoint {
+            coordinates: self.coordinates.clone().map(|(x, y)| (x, -y)),
+            curve: self.curve.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use finite_fields::PrimeField;
+    use rug::Integer;
+
+    use crate::counting::fixtures::{self, signed_element};
+
+    use super::{MontgomeryCurve, MontgomeryPoint};
+
+    // The points of a toy curve By^2 = x^3 + Ax^2 + x with 0 < x < 60
+    fn points(curve: &Arc<MontgomeryCurve>) -> Vec<MontgomeryPoint> {
+        fixtures::points(
+            curve.a().field(),
+            1..60,
+            |x| (x.clone() * x * x + curve.a().clone() * x * x + x) / curve.b(),
+            |x, y| curve.point(x, y),
+        )
+    }
+
+    #[test]
+    fn test_addition_commutes_with_weierstrass() {
+        let field = PrimeField::new(Integer::from(1009)).unwrap();
+        let curve = MontgomeryCurve::new(signed_element(&field, 6), signed_element(&field, 5));
+        let points = points(&curve);
+        assert!(points.len() > 10);
+
+        for p in points.iter() {
+            assert!(p.to_weierstrass().is_valid());
+            assert_eq!(MontgomeryPoint::from_weierstrass(&curve, &p.to_weierstrass()), *p);
+
+            for q in points.iter().chain([&-p, &curve.identity()]) {
+                assert_eq!((p + q).to_weierstrass(), &p.to_weierstrass() + &q.to_weierstrass());
+            }
+        }
+
+        // The point (0, 0) has order 2
+        let two_torsion = curve.point(field.zero(), field.zero());
+        assert_eq!(&two_torsion + &two_torsion, curve.identity());
+        assert_eq!(points[0].scalar_mul(Integer::from(5)).to_weierstrass(), points[0].to_weierstrass().scalar_mul(Integer::from(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "The curve is singular")]
+    fn test_singular() {
+        let field = PrimeField::new(Integer::from(1009)).unwrap();
+        MontgomeryCurve::new(signed_element(&field, 2), field.one());
+    }
+}
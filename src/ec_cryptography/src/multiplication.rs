@@ -0,0 +1,283 @@
+// Scalar multiplication.
+//
+// Arbitrary points use a width-5 wNAF: the scalar is rewritten with odd digits between -15
+// and 15, with at least four zeros after each of them, so only about one bit in six costs an
+// addition. Several scalars can share one chain of doublings (Strauss' method), which is how
+// `S256Field::verify` computes uG + vP. The generator of a named curve gets a comb table,
+// built on first use, that brings a 256-bit multiplication down to 32 doublings and 32
+// additions; in a multi-scalar multiplication its columns ride on the last 32 doublings.
+
+use std::{fmt::Debug, sync::Arc};
+
+use finite_fields::Field;
+use rug::{ops::RemRounding, Integer};
+
+use crate::{Curve, Point, JacobianPoint};
+
+/// Width of the wNAF digits
+const WINDOW: u32 = 5;
+
+/// Number of teeth of the generator comb, the table has 2^TEETH - 1 points
+const TEETH: u32 = 8;
+
+/// The width-`width` NAF of `scalar`, least significant digit first
+fn wnaf(scalar: &Integer, width: u32) -> Vec<i32> {
+    let modulus = 1i32 << width;
+    let mut remaining = scalar.clone().abs();
+    let mut digits = Vec::with_capacity(remaining.significant_bits() as usize + 1);
+
+    while remaining > 0 {
+        let mut digit = 0;
+        if remaining.is_odd() {
+            // The signed residue mod 2^width, clearing the next width - 1 bits
+            digit = remaining.mod_u(modulus as u32) as i32;
+            if digit > modulus / 2 {
+                digit -= modulus;
+            }
+            remaining -= digit;
+        }
+
+        digits.push(if *scalar < 0 { -digit } else { digit });
+        remaining >>= 1;
+    }
+    digits
+}
+
+/// P, 3P, 5P, ..., (2^(WINDOW - 1) - 1)P, the multiples needed by the wNAF digits
+fn odd_multiples<F: Field>(point: &Point<F>) -> Vec<JacobianPoint<F>> {
+    let first = JacobianPoint::from(point);
+    let twice = first.double();
+
+    let mut multiples = vec![first];
+    for i in 1..1 << (WINDOW - 2) {
+        let next = &multiples[i - 1] + &twice;
+        multiples.push(next);
+    }
+    multiples
+}
+
+// How one term of a multi-scalar multiplication is added into the doubling chain
+enum Expansion<'a, F> {
+    // wNAF digits and the odd multiples of the point
+    Wnaf(Vec<i32>, Vec<JacobianPoint<F>>),
+    // A multiple of the generator, reduced mod n, read column by column from the comb table
+    Comb(&'a CombTable<F>, Integer),
+}
+
+/// The sum of scalar * point over all the terms, with a single chain of doublings.
+/// All the points must be on the same curve and there must be at least one.
+pub fn multi_scalar_mul<F: Field>(terms: &[(&Integer, &Point<F>)]) -> Point<F> {
+    let (_, first) = terms.first().expect("multi_scalar_mul needs at least one term");
+
+    let expansions: Vec<Expansion<F>> = terms
+        .iter()
+        .map(|&(scalar, point)| match point.curve.generator_order() {
+            // The comb columns are added during the last doublings of the chain
+            Some(order) if point.curve.is_generator(point) => {
+                Expansion::Comb(point.curve.generator_table(), scalar.clone().rem_euc(order))
+            }
+            _ => Expansion::Wnaf(wnaf(scalar, WINDOW), odd_multiples(point)),
+        })
+        .collect();
+    let length = expansions
+        .iter()
+        .map(|expansion| match expansion {
+            Expansion::Wnaf(digits, _) => digits.len(),
+            Expansion::Comb(table, _) => table.spacing as usize,
+        })
+        .max()
+        .unwrap();
+
+    let mut result = JacobianPoint::identity(&first.curve);
+    for i in (0..length).rev() {
+        result = result.double();
+
+        for expansion in expansions.iter() {
+            match expansion {
+                Expansion::Wnaf(digits, multiples) => match digits.get(i) {
+                    Some(&digit) if digit > 0 => result = &result + &multiples[digit as usize / 2],
+                    Some(&digit) if digit < 0 => result = &result + &-&multiples[-digit as usize / 2],
+                    _ => {}
+                },
+                Expansion::Comb(table, scalar) if i < table.spacing as usize => {
+                    result = table.add_column(&result, scalar, i as u32);
+                }
+                Expansion::Comb(..) => {}
+            }
+        }
+    }
+    result.to_affine()
+}
+
+/// The comb table of a generator G.
+///
+/// With the scalar bits split into TEETH rows of `spacing` bits, the entry for a set of rows
+/// is the sum of 2^(row * spacing) G over them. Each column of bits is then one lookup.
+#[derive(Clone)]
+pub(crate) struct CombTable<F> {
+    spacing: u32,
+    // Affine points, so the additions are mixed. None for the identity, which toy curves can hit.
+    points: Vec<Option<(F, F)>>,
+}
+
+impl<F> Debug for CombTable<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "CombTable({} points)", self.points.len())
+    }
+}
+
+impl<F: Field> CombTable<F> {
+    /// The table for scalars of at most `bits` bits
+    pub(crate) fn new(generator: &Point<F>, bits: u32) -> CombTable<F> {
+        let spacing = bits.div_ceil(TEETH).max(1);
+
+        // 2^(row * spacing) G for each row
+        let mut rows = vec![JacobianPoint::from(generator)];
+        for row in 1..TEETH as usize {
+            let mut next = rows[row - 1].clone();
+            for _ in 0..spacing {
+                next = next.double();
+            }
+            rows.push(next);
+        }
+
+        // Each sum is a smaller one plus its highest row
+        let mut sums = vec![JacobianPoint::identity(&generator.curve)];
+        for index in 1..1usize << TEETH {
+            let highest = index.ilog2() as usize;
+            let sum = &sums[index - (1 << highest)] + &rows[highest];
+            sums.push(sum);
+        }
+
+        let points = sums[1..]
+            .iter()
+            .map(|sum| {
+                let point = sum.to_affine();
+                point.x.zip(point.y)
+            })
+            .collect();
+
+        CombTable { spacing, points }
+    }
+
+    /// scalar * G, for 0 <= scalar < 2^(TEETH * spacing)
+    pub(crate) fn mul(&self, curve: &Arc<Curve<F>>, scalar: &Integer) -> Point<F> {
+        let mut result = JacobianPoint::identity(curve);
+        for column in (0..self.spacing).rev() {
+            result = self.add_column(&result.double(), scalar, column);
+        }
+        result.to_affine()
+    }
+
+    /// Adds the entry for the bits of `scalar` in `column`
+    fn add_column(&self, point: &JacobianPoint<F>, scalar: &Integer, column: u32) -> JacobianPoint<F> {
+        let index = (0..TEETH)
+            .filter(|&row| scalar.get_bit(row * self.spacing + column))
+            .fold(0usize, |index, row| index | 1 << row);
+
+        match index.checked_sub(1).and_then(|entry| self.points[entry].as_ref()) {
+            Some((x, y)) => point.add_affine(x, y),
+            None => point.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::{rng, Fe256, FieldElement, Scalar};
+    use rug::Integer;
+
+    use crate::{s256_field::secp_generator_point, Curve, Point, JacobianPoint, SECP256K1};
+
+    use super::{multi_scalar_mul, wnaf, WINDOW};
+
+    // The double-and-add loop, as a reference
+    fn double_and_add<F: finite_fields::Field>(point: &Point<F>, scalar: &Integer) -> Point<F> {
+        let mut result = JacobianPoint::from(&point.identity());
+        for bit in (0..scalar.significant_bits()).rev() {
+            result = result.double();
+            if scalar.get_bit(bit) {
+                result = result.add_mixed(point);
+            }
+        }
+        result.to_affine()
+    }
+
+    #[test]
+    fn test_wnaf() {
+        for scalar in [0, 1, 7, 15, 16, 31, 1000, -1000, 0x7fff_ffff] {
+            let digits = wnaf(&Integer::from(scalar), WINDOW);
+
+            let value = digits.iter().rev().fold(Integer::new(), |acc, &digit| (acc << 1) + digit);
+            assert_eq!(value, scalar);
+
+            // Odd digits below 2^(w-1), followed by at least w-1 zeros
+            for (i, &digit) in digits.iter().enumerate().filter(|(_, &digit)| digit != 0) {
+                assert!(digit % 2 != 0 && digit.abs() < 16);
+                assert!(digits[i + 1..].iter().take(WINDOW as usize - 1).all(|&next| next == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_double_and_add() {
+        let element = |num: u32| FieldElement::new(Integer::from(num), Integer::from(223));
+        let curve = Curve::new(element(0), element(7));
+        let point = curve.point(element(47), element(71));
+
+        for scalar in 0..50 {
+            let scalar = Integer::from(scalar);
+            assert_eq!(point.scalar_mul(scalar.clone()), double_and_add(&point, &scalar));
+        }
+        // A negative scalar multiplies the negated point
+        assert_eq!(point.scalar_mul(Integer::from(-4)), -point.scalar_mul(Integer::from(4)));
+
+        let mut rng = rng::deterministic(5);
+        let point = secp_generator_point().scalar_mul(Integer::from(5000));
+        for _ in 0..5 {
+            let scalar = Scalar::random(&mut rng).to_integer();
+            assert_eq!(point.scalar_mul(scalar.clone()), double_and_add(&point, &scalar));
+        }
+    }
+
+    #[test]
+    fn test_generator_comb() {
+        let mut rng = rng::deterministic(6);
+        let generator = secp_generator_point();
+        let order = SECP256K1.generator_order().unwrap();
+
+        let mut scalars = vec![Integer::from(1), Integer::from(2), order.clone() - 1, order.clone() + 5];
+        scalars.extend((0..5).map(|_| Scalar::random(&mut rng).to_integer()));
+        for scalar in scalars {
+            // scalar_mul goes through the comb table for the generator
+            assert_eq!(generator.scalar_mul(scalar.clone()), double_and_add(&generator, &scalar));
+        }
+        assert_eq!(generator.scalar_mul(order.clone()), generator.identity());
+
+        // The same for the fixed-width copy of the curve
+        let fixed_width = Point::<Fe256>::try_from(&generator).unwrap();
+        assert_eq!(fixed_width.scalar_mul(Integer::from(5000)), Point::try_from(&generator.scalar_mul(Integer::from(5000))).unwrap());
+    }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        let mut rng = rng::deterministic(8);
+        let generator = secp_generator_point();
+        let point = generator.scalar_mul(Integer::from(5000));
+
+        for _ in 0..5 {
+            let u = Scalar::random(&mut rng).to_integer();
+            let v = Scalar::random(&mut rng).to_integer();
+
+            // The generator term goes through the comb, the other one through its wNAF
+            assert_eq!(
+                multi_scalar_mul(&[(&u, &generator), (&v, &point)]),
+                double_and_add(&generator, &u) + double_and_add(&point, &v)
+            );
+        }
+
+        // Terms that cancel out
+        let (one, minus_one) = (Integer::from(1), Integer::from(-1));
+        assert_eq!(multi_scalar_mul(&[(&one, &point), (&minus_one, &point)]), point.identity());
+    }
+}
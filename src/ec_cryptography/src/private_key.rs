@@ -0,0 +1,176 @@
+// ECDSA signing on secp256k1.
+//
+// A signature of the hash z with the secret e and a nonce k is r = (kG).x mod n and
+// s = (z + re) / k mod n. Everything that touches e or k is computed with the constant-time
+// ladder and the `ct_*` scalar operations. s and n - s both verify, so the signature is
+// normalized to the low one as required by BIP 62 and Bitcoin's standardness rules. The
+// nonce comes from RFC 6979 unless one is given, see the `rfc6979` module.
+
+use std::fmt::{Debug, Formatter};
+
+use finite_fields::{rng::{CryptoRng, RngCore}, Scalar};
+use rug::Integer;
+
+use crate::{rfc6979::Nonces, s256_field::{secp_generator_point, S256Field, Signature}, Point};
+
+/// A secp256k1 private key, the secret e in [1, n) and its public point eG
+pub struct PrivateKey {
+    secret: Scalar,
+    point: Point,
+}
+
+/// Only the public point is printed, never the secret
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "PrivateKey(public={:?})", self.point)
+    }
+}
+
+impl PrivateKey {
+    /// Panics if the secret is zero
+    pub fn new(secret: Scalar) -> PrivateKey {
+        assert!(!secret.is_zero(), "The secret must not be zero");
+
+        // The secret is multiplied with the constant-time ladder
+        let point = &secp_generator_point() * &secret;
+        PrivateKey { secret, point }
+    }
+
+    /// A new key with a uniformly random secret. Pass `rng::OsRng` for real keys.
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> PrivateKey {
+        PrivateKey::new(Scalar::random_nonzero(rng))
+    }
+
+    /// The public point eG
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    /// The public key, to verify the signatures with
+    pub fn public_key(&self) -> S256Field {
+        S256Field::from_point(&self.point)
+    }
+
+    /// Signs the hash z with the deterministic nonce of RFC 6979
+    pub fn sign(&self, z: Integer) -> Signature {
+        self.sign_with_nonces(&z, Nonces::new(&self.secret, &z, None))
+    }
+
+    /// Signs the hash z with an RFC 6979 nonce that also depends on 32 bytes of extra entropy,
+    /// e.g a counter to grind for a short r, or random bytes
+    pub fn sign_with_entropy(&self, z: Integer, extra_entropy: &[u8; 32]) -> Signature {
+        self.sign_with_nonces(&z, Nonces::new(&self.secret, &z, Some(extra_entropy)))
+    }
+
+    /// Signs with the first candidate nonce that gives non-zero r and s
+    fn sign_with_nonces(&self, z: &Integer, mut nonces: Nonces) -> Signature {
+        nonces.find_map(|nonce| self.sign_with_nonce(z, &nonce)).unwrap()
+    }
+
+    /// Signs the hash z with the nonce k, None in the unlikely case that r or s is zero.
+    /// Two signatures with the same nonce reveal the secret, the nonce must never be reused.
+    pub fn sign_with_nonce(&self, z: &Integer, nonce: &Scalar) -> Option<Signature> {
+        let point = &secp_generator_point() * nonce;
+        let r = Scalar::from_integer(&point.x()?.num());
+        if r.is_zero() {
+            return None;
+        }
+
+        // s = (z + re) / k
+        let z = Scalar::from_integer(z);
+        let mut s = z.ct_add(&r.ct_mul(&self.secret)).ct_mul(&nonce.ct_invert());
+        if s.is_zero() {
+            return None;
+        }
+        if s.is_high() {
+            s = s.ct_neg();
+        }
+
+        Some(Signature::new(r.to_integer(), s.to_integer()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::{rng, Scalar};
+    use rug::Integer;
+
+    use crate::s256_field::Signature;
+
+    use super::PrivateKey;
+
+    fn hex(digits: &str) -> Integer {
+        Integer::from_str_radix(digits, 16).unwrap()
+    }
+
+    #[test]
+    fn test_sign_with_nonce() {
+        // The example of Programming Bitcoin: e = 12345, z = hash256("Programming Bitcoin!")
+        let key = PrivateKey::new(Scalar::from_u64(12345));
+        let z = hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48");
+
+        let signature = key.sign_with_nonce(&z, &Scalar::from_u64(1234567890)).unwrap();
+        assert_eq!(
+            signature,
+            Signature::new(
+                hex("2b698a0f0a4041b77e63488ad48c23e8e8838dd1fb7520408b121697b782ef22"),
+                hex("1dbc63bfef4416705e602a7b564161167076d8b20990a0f26f316cff2cb0bc1a"),
+            )
+        );
+        assert_eq!(key.point().x().unwrap().num(), hex("f01d6b9018ab421dd410404cb869072065522bf85734008f105cf385a023a80f"));
+        assert!(key.public_key().verify(z, signature));
+    }
+
+    #[test]
+    fn test_deterministic_signatures() {
+        // sha256("Satoshi Nakamoto") signed with the secret 1, as in the RFC 6979 test vectors
+        let key = PrivateKey::new(Scalar::ONE);
+        let z = hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+
+        let signature = key.sign(z.clone());
+        assert_eq!(
+            signature,
+            Signature::new(
+                hex("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"),
+                hex("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"),
+            )
+        );
+        assert_eq!(key.sign(z.clone()), signature);
+
+        // Extra entropy changes the nonce, the signature still verifies
+        let ground = key.sign_with_entropy(z.clone(), &[1; 32]);
+        assert_ne!(ground.r(), signature.r());
+        assert!(key.public_key().verify(z, ground));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let mut rng = rng::deterministic(11);
+        for _ in 0..5 {
+            let key = PrivateKey::random(&mut rng);
+            let z = Scalar::random(&mut rng).to_integer();
+
+            let signature = key.sign(z.clone());
+            // Low S
+            assert!(!Scalar::from_integer(signature.s()).is_high());
+            assert!(key.public_key().verify(z.clone(), signature.clone()));
+
+            // Another message, or another key, does not verify
+            assert!(!key.public_key().verify(z.clone() + 1, signature.clone()));
+            assert!(!PrivateKey::random(&mut rng).public_key().verify(z, signature));
+        }
+    }
+
+    #[test]
+    fn test_high_s_is_normalized() {
+        let key = PrivateKey::new(Scalar::from_u64(12345));
+        let z = Integer::from(1);
+
+        // k and n - k give the same r and opposite s, one of them high before normalization
+        let nonce = Scalar::from_u64(1234567890);
+        let signature = key.sign_with_nonce(&z, &nonce).unwrap();
+        assert_eq!(key.sign_with_nonce(&z, &-nonce).unwrap(), signature);
+        assert!(!Scalar::from_integer(signature.s()).is_high());
+        assert!(key.public_key().verify(z, signature));
+    }
+}
@@ -0,0 +1,138 @@
+// Deterministic ECDSA nonces (RFC 6979) with HMAC-SHA256.
+//
+// The nonce is derived from the secret and the message hash by an HMAC-based generator, so
+// signing the same message twice gives the same signature, and different messages never share
+// a nonce unless the hash collides. Extra entropy is mixed into the seed after the hash, as
+// libsecp256k1 does: Bitcoin Core uses a counter there to grind for signatures with a short r,
+// and random bytes make the nonce unpredictable even to someone who knows the message.
+
+use finite_fields::Scalar;
+use hmac::{Hmac, Mac};
+use rug::Integer;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// HMAC-SHA256 of the concatenated parts
+fn hmac(key: &[u8; 32], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).unwrap();
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// The candidate nonces of RFC 6979 section 3.2, in order. Signing takes the first one that
+/// gives a signature with non-zero r and s.
+pub struct Nonces {
+    k: [u8; 32],
+    v: [u8; 32],
+    first: bool,
+}
+
+impl Nonces {
+    /// The nonces for the secret and the hash z, reduced mod n, with optional extra entropy
+    pub fn new(secret: &Scalar, z: &Integer, extra_entropy: Option<&[u8; 32]>) -> Nonces {
+        let secret = secret.to_be_bytes();
+        let hash = Scalar::from_integer(z).to_be_bytes();
+        let extra: &[u8] = extra_entropy.map_or(&[], |entropy| entropy);
+
+        let (mut k, mut v) = ([0u8; 32], [1u8; 32]);
+        for separator in [0u8, 1] {
+            k = hmac(&k, &[&v, &[separator], &secret, &hash, extra]);
+            v = hmac(&k, &[&v]);
+        }
+        Nonces { k, v, first: true }
+    }
+}
+
+impl Iterator for Nonces {
+    type Item = Scalar;
+
+    fn next(&mut self) -> Option<Scalar> {
+        loop {
+            if !self.first {
+                self.k = hmac(&self.k, &[&self.v, &[0]]);
+                self.v = hmac(&self.k, &[&self.v]);
+            }
+            self.first = false;
+
+            // 256 bits for a 256-bit order, kept only if it is in [1, n)
+            self.v = hmac(&self.k, &[&self.v]);
+            match Scalar::from_be_bytes(&self.v) {
+                Some(nonce) if !nonce.is_zero() => return Some(nonce),
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// The RFC 6979 nonce for signing the hash z with the secret
+pub fn nonce(secret: &Scalar, z: &Integer) -> Scalar {
+    Nonces::new(secret, z, None).next().unwrap()
+}
+
+/// The nonce with 32 bytes of extra entropy mixed in
+pub fn nonce_with_entropy(secret: &Scalar, z: &Integer, extra_entropy: &[u8; 32]) -> Scalar {
+    Nonces::new(secret, z, Some(extra_entropy)).next().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::Scalar;
+    use rug::{integer::Order, Integer};
+    use sha2::{Digest, Sha256};
+
+    use super::{nonce, nonce_with_entropy, Nonces};
+
+    fn hex(digits: &str) -> Integer {
+        Integer::from_str_radix(digits, 16).unwrap()
+    }
+
+    fn sha256(message: &str) -> Integer {
+        Integer::from_digits(&Sha256::digest(message.as_bytes()), Order::Msf)
+    }
+
+    #[test]
+    fn test_vectors() {
+        // The secp256k1 / SHA-256 vectors used by python-ecdsa, bitcoinjs and Trezor
+        let vectors = [
+            ("1", "Satoshi Nakamoto", "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15"),
+            (
+                "1",
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "Satoshi Nakamoto",
+                "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+            ),
+            (
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "Alan Turing",
+                "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+            ),
+        ];
+
+        for (secret, message, expected) in vectors {
+            let secret = Scalar::from_integer(&hex(secret));
+            assert_eq!(nonce(&secret, &sha256(message)).to_integer(), hex(expected), "Wrong nonce for {:?}", message);
+        }
+    }
+
+    #[test]
+    fn test_extra_entropy() {
+        let (secret, z) = (Scalar::ONE, sha256("Satoshi Nakamoto"));
+
+        let nonce_with_ones = nonce_with_entropy(&secret, &z, &[1; 32]);
+        assert_eq!(nonce_with_ones.to_integer(), hex("ff49282725ee554d481ee92230ebf201d5137cdc427fcda67210387e20a1b90b"));
+        assert_ne!(nonce_with_entropy(&secret, &z, &[2; 32]), nonce_with_ones);
+        assert_ne!(nonce(&secret, &z), nonce_with_ones);
+
+        // The candidates after the first are distinct too
+        let candidates: Vec<Scalar> = Nonces::new(&secret, &z, None).take(3).collect();
+        assert_eq!(candidates[0], nonce(&secret, &z));
+        assert!(candidates[0] != candidates[1] && candidates[1] != candidates[2]);
+    }
+}
@@ -1,10 +1,10 @@
-use finite_fields::FieldElement;
+use finite_fields::{Fe256, FieldElement, PrimeField, Scalar};
 use rug::integer::Order;
-use rug::{Integer, Complete};
-use rug::ops::Pow;
+use rug::Integer;
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
-use crate::EllipticCurve;
+use crate::{error::DerError, multi_scalar_mul, Point, SECP256K1};
 
 pub struct S256Field {
     x: Option<FieldElement>,
@@ -16,63 +16,64 @@ impl S256Field {
         S256Field { x: None, y: None }
     }
 
+    /// The public key of a secp256k1 point
+    pub fn from_point(point: &Point) -> S256Field {
+        S256Field { x: point.x().cloned(), y: point.y().cloned() }
+    }
+
+    /// True if the signature of the hash z is valid for this public key. r and s must be in
+    /// [1, n - 1]: reducing them mod n would let r + n or s + n verify too.
     pub fn verify(&self, z: Integer, signature: Signature) -> bool {
-        let s = FieldElement::new(signature.s.clone(), Self::order());
-        let z = FieldElement::new(z, Self::order());
-        let r = FieldElement::new(signature.r.clone(), Self::order());
+        let order = S256Field::order();
+        if [&signature.r, &signature.s].iter().any(|&value| *value < 1 || *value >= order) {
+            return false;
+        }
 
-        let u = z / s.clone();
-        let v = r.clone() / s;
+        let s_inverse = match Scalar::from_integer(&signature.s).invert() {
+            Some(s_inverse) => s_inverse,
+            None => return false,
+        };
+        let z = Scalar::from_integer(&z);
+        let r = Scalar::from_integer(&signature.r);
 
-        let generator = secp_generator_point();
-        let point = self.to_point();
+        let u = z * s_inverse;
+        let v = r * s_inverse;
 
-        let result = generator.scalar_mul(u.num()) + point.scalar_mul(v.num());
+        // uG + vP in one pass, on the fixed-width arithmetic
+        let generator = Point::<Fe256>::try_from(&secp_generator_point()).unwrap();
+        let point = Point::<Fe256>::try_from(&self.to_point()).unwrap();
 
-        result.x.unwrap().num() == r.num()
-    }
+        let result = multi_scalar_mul(&[(&u.to_integer(), &generator), (&v.to_integer(), &point)]);
 
-    pub fn to_point(&self) -> EllipticCurve {
-        let prime = Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977);
+        match result.x {
+            Some(x) => Scalar::from_integer(&x.to_integer()) == r,
+            None => false,
+        }
+    }
 
-        EllipticCurve::new(
-            self.x.clone(),
-            self.y.clone(),
-            FieldElement::new(Integer::ZERO, prime.clone()),
-            FieldElement::new(Integer::from(7), prime)
-        )
+    pub fn to_point(&self) -> Point {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => SECP256K1.point(x.clone(), y.clone()),
+            _ => SECP256K1.identity(),
+        }
     }
 
     pub fn order() -> Integer {
-        Integer::parse_radix(
-            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
-             16
-        )
-            .unwrap()
-            .complete()
+        SECP256K1.generator_order().unwrap().clone()
     }
 }
 
-pub fn secp_generator_point() -> EllipticCurve {
-    let prime = Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977);
-
-    let a = FieldElement::new(Integer::ZERO, prime.clone());
-    let b = FieldElement::new(Integer::from(7), prime.clone());
-
-    let gx = Integer::parse_radix("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798", 16).unwrap().complete();
-    let gy = Integer::parse_radix("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8", 16).unwrap().complete();
-
-    let x = FieldElement::new(gx, prime.clone());
-    let y = FieldElement::new(gy, prime.clone());
+/// The field of the secp256k1 coordinates, p = 2^256 - 2^32 - 977.
+/// It is created once and shared by every secp256k1 point.
+pub fn secp256k1_field() -> Arc<PrimeField> {
+    Fe256::field()
+}
 
-    EllipticCurve::new(
-        Some(x.clone()),
-        Some(y.clone()),
-        a.clone(),
-        b.clone()
-    )
+pub fn secp_generator_point() -> Point {
+    SECP256K1.generator().unwrap()
 }
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct Signature {
     r: Integer,
     s: Integer,
@@ -89,41 +90,104 @@ impl Signature {
         Signature { r, s }
     }
 
-    /// This is the Distingished Encoding Rule for encoding Signatures
-    pub fn der(&self) -> String {
-        let prefix = "30";
+    pub fn r(&self) -> &Integer {
+        &self.r
+    }
+
+    pub fn s(&self) -> &Integer {
+        &self.s
+    }
 
-        // calculate the length of the signature
-        let signature_length = self.length();
-        println!("Signature length: {}", signature_length);
+    /// The DER encoding, 0x30, the length, then r and s as DER integers (0x02, length, bytes).
+    /// Fails if r or s is negative or too large to be read back by `from_der`.
+    pub fn to_der(&self) -> Result<Vec<u8>, DerError> {
+        let mut integers = vec![];
+        for value in [&self.r, &self.s] {
+            if *value < 0 {
+                return Err(DerError::NegativeInteger);
+            }
+            let (length, integer) = self.der_integer_length(value.to_digits::<u8>(Order::MsfBe));
+            if length > 33 {
+                return Err(DerError::IntegerTooLarge);
+            }
+            integers.push(integer);
+        }
 
-        let (r_length, r) = self.der_integer_length(self.r.to_digits::<u8>(Order::MsfBe));
-        let (s_length, s) = self.der_integer_length(self.s.to_digits::<u8>(Order::MsfBe));
-        println!("r length: {}", r_length);
-        println!("s length: {}", s_length);
+        // At most 2 * (2 + 33) = 70 bytes, the lengths fit in one byte
+        let mut der = vec![0x30, (integers[0].len() + integers[1].len() + 4) as u8];
+        for integer in integers {
+            der.extend([0x02, integer.len() as u8]);
+            der.extend(integer);
+        }
+        Ok(der)
+    }
 
-        prefix.to_string()
+    /// This is the Distingished Encoding Rule for encoding Signatures, as a hex string
+    pub fn der(&self) -> Result<String, DerError> {
+        Ok(self.to_der()?.iter().map(|byte| format!("{:02x}", byte)).collect())
     }
 
-    pub fn length(&self) -> usize {
-        let r = self.r.to_digits::<u8>(Order::MsfBe);
-        let s = self.s.to_digits::<u8>(Order::MsfBe);
+    /// Parses a DER signature with the strict rules of BIP 66: the minimal encoding of two
+    /// non-negative integers and nothing else. There is no sighash byte at the end.
+    pub fn from_der(der: &[u8]) -> Result<Signature, DerError> {
+        // 0x30, the length, and two integers of 1 to 33 bytes
+        if der.len() < 8 || der.len() > 72 {
+            return Err(DerError::InvalidLength(der.len()));
+        }
+        if der[0] != 0x30 {
+            return Err(DerError::NotASequence);
+        }
+        if der[1] as usize != der.len() - 2 {
+            return Err(DerError::SequenceLength);
+        }
+
+        let (r, rest) = Signature::der_integer(&der[2..])?;
+        let (s, rest) = Signature::der_integer(rest)?;
+        if !rest.is_empty() {
+            return Err(DerError::TrailingBytes);
+        }
 
-        let r_len = self.der_integer_length(r);
-        let s_len = self.der_integer_length(s);
+        Ok(Signature::new(r, s))
+    }
 
-        1 + 1 + r_len.0 + s_len.0
+    /// The number of bytes of the DER encoding
+    pub fn length(&self) -> Result<usize, DerError> {
+        Ok(self.to_der()?.len())
     }
 
-    pub fn der_integer_length(&self, mut data: Vec<u8>) -> (usize, Vec<u8>) {
-        let mut length = data.len();
-        if data[0] & 0x80 >= 0x80 {
-            println!("Data: {:?}", data);
-            data.insert(0, 00);
-            length += 1;
+    /// The length and bytes of a big-endian number as a DER integer: the leading zeros are
+    /// stripped, and a zero byte is added when the high bit is set so it does not read as negative
+    pub fn der_integer_length(&self, data: Vec<u8>) -> (usize, Vec<u8>) {
+        let start = data.iter().position(|&byte| byte != 0).unwrap_or(data.len());
+        let mut integer = data[start..].to_vec();
+        if integer.first().is_none_or(|&byte| byte & 0x80 != 0) {
+            integer.insert(0, 0);
         }
 
-        (length, data)
+        (integer.len(), integer)
+    }
+
+    /// Reads one DER integer, returning it and the bytes after it
+    fn der_integer(der: &[u8]) -> Result<(Integer, &[u8]), DerError> {
+        match der {
+            [0x02, length, rest @ ..] => {
+                let length = *length as usize;
+                if length > rest.len() {
+                    return Err(DerError::SequenceLength);
+                }
+
+                let bytes = &rest[..length];
+                match bytes {
+                    [] => return Err(DerError::EmptyInteger),
+                    [first, ..] if first & 0x80 != 0 => return Err(DerError::NegativeInteger),
+                    // A zero byte is only there to clear the high bit of the next one
+                    [0, second, ..] if second & 0x80 == 0 => return Err(DerError::ExcessivePadding),
+                    _ => {}
+                }
+                Ok((Integer::from_digits(bytes, Order::MsfBe), &rest[length..]))
+            }
+            _ => Err(DerError::NotAnInteger),
+        }
     }
 }
 
@@ -131,7 +195,15 @@ impl Signature {
 mod tests {
     use rug::{integer::Order, Integer};
 
-    use super::Signature;
+    use finite_fields::Scalar;
+
+    use crate::{error::DerError, PrivateKey};
+
+    use super::{S256Field, Signature};
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
 
     #[test]
     fn test_der_encryption() {
@@ -140,8 +212,79 @@ mod tests {
             Integer::from_str_radix("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec", 16).unwrap()
         );
 
+        // s has its high bit set, so it gets a zero byte in front
         let der_r = signature.der_integer_length(signature.r.to_digits::<u8>(Order::MsfBe));
-        println!("DER R length: {}", der_r.0);
-        println!("DER R value: {:?}", der_r.1);
+        assert_eq!(der_r.0, 32);
+        let der_s = signature.der_integer_length(signature.s.to_digits::<u8>(Order::MsfBe));
+        assert_eq!((der_s.0, der_s.1[0]), (33, 0));
+
+        let der = "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c60221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec";
+        assert_eq!(signature.der().unwrap(), der);
+        assert_eq!(signature.length(), Ok(71));
+        assert_eq!(Signature::from_der(&signature.to_der().unwrap()), Ok(signature));
+    }
+
+    #[test]
+    fn test_der_integers() {
+        let signature = Signature::new(Integer::from(0), Integer::from(0x80));
+
+        // Leading zeros are stripped, zero is a single zero byte, and empty input does not panic
+        assert_eq!(signature.der_integer_length(vec![0, 0, 0x12, 0x34]), (2, vec![0x12, 0x34]));
+        assert_eq!(signature.der_integer_length(vec![]), (1, vec![0]));
+        assert_eq!(signature.to_der(), Ok(bytes("300702010002020080")));
+        assert_eq!(Signature::from_der(&signature.to_der().unwrap()), Ok(signature));
+
+        // 33 bytes is the longest DER integer from_der reads, 2^1024 would overflow the lengths
+        let largest: Integer = Integer::from(Integer::u_pow_u(2, 263)) - 1;
+        let signature = Signature::new(largest.clone(), Integer::from(1));
+        assert_eq!(Signature::from_der(&signature.to_der().unwrap()), Ok(signature));
+        for (r, s) in [(largest + 1, Integer::from(1)), (Integer::from(1), Integer::from(Integer::u_pow_u(2, 1024)))] {
+            assert_eq!(Signature::new(r, s).to_der(), Err(DerError::IntegerTooLarge));
+        }
+
+        // A negative value would be encoded as its magnitude
+        assert_eq!(Signature::new(Integer::from(-5), Integer::from(1)).to_der(), Err(DerError::NegativeInteger));
+    }
+
+    #[test]
+    fn test_strict_der() {
+        let valid = bytes("3006020101020101");
+        assert_eq!(Signature::from_der(&valid), Ok(Signature::new(Integer::from(1), Integer::from(1))));
+
+        let invalid = [
+            ("30050201010201", DerError::InvalidLength(7)),
+            ("3106020101020101", DerError::NotASequence),
+            ("3007020101020101", DerError::SequenceLength),
+            ("3006030101020101", DerError::NotAnInteger),
+            ("3006020501020101", DerError::SequenceLength),
+            ("3006020002010101", DerError::EmptyInteger),
+            ("3006020181020101", DerError::NegativeInteger),
+            ("300702020001020101", DerError::ExcessivePadding),
+            ("300702010102010100", DerError::TrailingBytes),
+        ];
+        for (der, error) in invalid {
+            assert_eq!(Signature::from_der(&bytes(der)), Err(error), "{} should be rejected", der);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_verify_range() {
+        let key = PrivateKey::new(Scalar::from_u64(12345));
+        let z = Integer::from(1);
+        let signature = key.sign(z.clone());
+        assert!(key.public_key().verify(z.clone(), signature.clone()));
+
+        // r + n and s + n are the same mod n, but are not valid signatures
+        let order = S256Field::order();
+        let (r, s) = (signature.r().clone(), signature.s().clone());
+        for (r, s) in [
+            (r.clone() + &order, s.clone()),
+            (r.clone(), s.clone() + &order),
+            (r.clone() - &order, s.clone()),
+            (Integer::ZERO, s.clone()),
+            (r, Integer::ZERO),
+        ] {
+            assert!(!key.public_key().verify(z.clone(), Signature::new(r, s)));
+        }
+    }
+}
@@ -1,47 +1,45 @@
-use rug::{integer::Order, Integer};
-
-use crate::{s256_field::Signature, traits::Serializer, EllipticCurve};
-
-impl Serializer for EllipticCurve {
-    fn sec(&self, is_compressed: bool) -> String {
-        // Uncompressed format serialization of a a pubkey
-        let serialized_x = self.x.clone().unwrap().num().to_string_radix(16);
-
-        if is_compressed {
-            let prefix = if self.y.clone().unwrap().num().is_odd() {
-                "03"
-            } else {
-                "02"
-            };
-            prefix.to_string() + &pad_or_truncate_data(serialized_x)
-        } else {
-            let prefix = "04";
-            let serialized_y = self.y.clone().unwrap().num().to_string_radix(16);
-            prefix.to_string() + &pad_or_truncate_data(serialized_x) + &pad_or_truncate_data(serialized_y)
-        }
+use finite_fields::Fe256;
+
+use crate::{traits::Serializer, Point};
+
+impl Serializer for Point {
+    fn sec_bytes(&self, is_compressed: bool) -> Vec<u8> {
+        let (x, y) = coordinates(self);
+        encode(x.to_bytes(), y.to_bytes(), y.num().is_odd(), is_compressed)
+    }
+}
+
+impl Serializer for Point<Fe256> {
+    fn sec_bytes(&self, is_compressed: bool) -> Vec<u8> {
+        let (x, y) = coordinates(self);
+        encode(x.to_be_bytes().to_vec(), y.to_be_bytes().to_vec(), y.is_odd(), is_compressed)
+    }
+}
+
+fn coordinates<F>(point: &Point<F>) -> (&F, &F) {
+    match (&point.x, &point.y) {
+        (Some(x), Some(y)) => (x, y),
+        _ => panic!("The point at infinity has no SEC encoding"),
     }
 }
 
-fn pad_or_truncate_data(input: String) -> String {
-    let mut data = input;
-    if data.len() < 64 {
-        let padding = "0".repeat(64 - data.len());
-        padding + &data
-    } else if data.len() > 64 {
-        data.truncate(64);
-        data
+// x and y are big-endian and padded to the size of the field
+fn encode(x: Vec<u8>, y: Vec<u8>, y_is_odd: bool, is_compressed: bool) -> Vec<u8> {
+    if is_compressed {
+        let prefix = if y_is_odd { 0x03 } else { 0x02 };
+        [vec![prefix], x].concat()
     } else {
-        data
+        [vec![0x04], x, y].concat()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
-
     use rug::{ops::Pow, Integer};
 
-    use crate::{s256_field::secp_generator_point, traits::Serializer};
+    use finite_fields::Fe256;
+
+    use crate::{s256_field::secp_generator_point, traits::Serializer, Point};
 
     #[test]
     fn test_uncompressed_sec() {
@@ -108,4 +106,17 @@ mod tests {
             "Serialized point is incorrect"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sec_bytes() {
+        let point = secp_generator_point().scalar_mul(Integer::from(5001));
+        let fixed_width = Point::<Fe256>::try_from(&point).unwrap();
+
+        for is_compressed in [true, false] {
+            let bytes = point.sec_bytes(is_compressed);
+            assert_eq!(bytes.len(), if is_compressed { 33 } else { 65 });
+            assert_eq!(fixed_width.sec_bytes(is_compressed), bytes);
+            assert_eq!(fixed_width.sec(is_compressed), point.sec(is_compressed));
+        }
+    }
+}
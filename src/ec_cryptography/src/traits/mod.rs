@@ -1,3 +1,10 @@
+/// The SEC encoding of a point over a prime field
 pub trait Serializer {
-    fn sec(&self, is_compressed: bool) -> String;
-}
\ No newline at end of file
+    /// 33 bytes compressed (02 or 03 for the parity of y, then x) or 65 uncompressed (04, x and y)
+    fn sec_bytes(&self, is_compressed: bool) -> Vec<u8>;
+
+    /// The SEC encoding as a hex string
+    fn sec(&self, is_compressed: bool) -> String {
+        self.sec_bytes(is_compressed).iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
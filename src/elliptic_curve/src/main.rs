@@ -1,5 +1,5 @@
 use elliptic_curve::EllipticPoint;
-use rug::Integer;
+use rug::Rational;
 
 fn main() {
     // Invalid
@@ -8,19 +8,19 @@ fn main() {
 
 
     let point_2 = EllipticPoint::new(
-        Some(Integer::from(-1)),
-         Some(Integer::from(-1)), 
-         Integer::from(5), 
-        Integer::from(7)
+        Some(Rational::from(-1)),
+         Some(Rational::from(-1)), 
+         Rational::from(5), 
+        Rational::from(7)
     );
     println!("Point 1 is valid: {}", point_2.is_valid());
 
 
     let point_3 = EllipticPoint::new(
-        Some(Integer::from(18)), 
-        Some(Integer::from(77)), 
-        Integer::from(5), 
-       Integer::from(7)
+        Some(Rational::from(18)), 
+        Some(Rational::from(77)), 
+        Rational::from(5), 
+       Rational::from(7)
     );
     println!("Point 1 is valid: {}", point_3.is_valid());
 
@@ -29,10 +29,10 @@ fn main() {
     // println!("Point 1 is valid: {}", point_4.is_valid());
 
     let point_a = EllipticPoint::new(
-        Some(Integer::from(47)),
-         Some(Integer::from(71)), 
-         Integer::from(0), 
-        Integer::from(7)
+        Some(Rational::from(47)),
+         Some(Rational::from(71)), 
+         Rational::from(0), 
+        Rational::from(7)
     );
     println!("Poin A + Point A: {:?}", point_a.clone() + point_a);
 }
@@ -0,0 +1,348 @@
+// Pictures of curves, as SVG for the docs or CSV for a spreadsheet.
+//
+// Over the reals, y^2 = x^3 + ax + b is drawn by sampling x and taking both square roots; the
+// cubic is negative between its real roots, so the curve is one or two pieces, each a loop of
+// the upper branch followed by the lower one. An addition P + Q adds the chord through P and Q
+// (the tangent when P = Q), the third intersection -R, and the vertical line from -R to its
+// mirror image R = P + Q. Over a small prime field the curve is just the scatter of its points.
+
+use std::{fmt::Write, io, path::Path, sync::Arc};
+
+use ec_cryptography::Curve;
+use rug::Rational;
+
+use crate::EllipticPoint;
+
+/// Size of the SVG pictures, in pixels
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 600.0;
+
+/// Samples of x across the picture of a real curve
+const SAMPLES: usize = 1000;
+
+// A line segment, dashed for the construction lines
+struct Segment {
+    from: (f64, f64),
+    to: (f64, f64),
+    dashed: bool,
+}
+
+/// A picture of a curve, its labelled points and construction lines
+pub struct Plot {
+    // The window, x_min, x_max, y_min, y_max
+    view: (f64, f64, f64, f64),
+    // The pieces of a real curve, each a polyline
+    curves: Vec<Vec<(f64, f64)>>,
+    segments: Vec<Segment>,
+    points: Vec<((f64, f64), String)>,
+}
+
+impl Plot {
+    /// The real curve y^2 = x^3 + ax + b
+    pub fn real_curve(curve: &Curve<Rational>) -> Plot {
+        Plot::with_real_curve(curve, &[])
+    }
+
+    /// The real curve of P and Q with the chord-and-tangent construction of P + Q. Panics if
+    /// either point is the point at infinity.
+    pub fn addition(p: &EllipticPoint, q: &EllipticPoint) -> Plot {
+        let (p_coordinates, q_coordinates) = (coordinates(p), coordinates(q));
+        let sum = p + q;
+
+        let mut labelled = vec![(p_coordinates, "P".to_string()), (q_coordinates, "Q".to_string())];
+        if p == q {
+            labelled.truncate(1);
+            labelled[0].1 = "P = Q".to_string();
+        }
+        let third = (!sum.is_identity()).then(|| coordinates(&-&sum));
+        if let Some((x, y)) = third {
+            labelled.push(((x, y), "-R".to_string()));
+            labelled.push(((x, -y), "R = P + Q".to_string()));
+        }
+
+        let mut plot = Plot::with_real_curve(p.curve(), &labelled);
+        let (x_min, x_max, y_min, y_max) = plot.view;
+
+        let slope = if p == q { p.tangent_slope() } else { p.slope(q) };
+        match slope {
+            // The chord or tangent across the window, then down (or up) to the sum
+            Some(slope) => {
+                let (slope, (x, y)) = (slope.to_f64(), p_coordinates);
+                plot.segments.push(Segment {
+                    from: (x_min, y + slope * (x_min - x)),
+                    to: (x_max, y + slope * (x_max - x)),
+                    dashed: false,
+                });
+                let (x, y) = third.unwrap();
+                plot.segments.push(Segment { from: (x, y), to: (x, -y), dashed: true });
+            }
+            // A vertical chord meets the curve again at infinity, P + Q = O
+            None => {
+                let x = p_coordinates.0;
+                plot.segments.push(Segment { from: (x, y_min), to: (x, y_max), dashed: false });
+            }
+        }
+        plot
+    }
+
+    /// The scatter of all the points of a curve over a small prime field
+    pub fn field_points(curve: &Arc<Curve>) -> Plot {
+        let prime = curve.prime().to_f64();
+        let points = curve
+            .points()
+            .iter()
+            .filter_map(|point| Some(((point.x()?.num().to_f64(), point.y()?.num().to_f64()), String::new())))
+            .collect();
+
+        Plot {
+            view: (-1.0, prime, -1.0, prime),
+            curves: vec![],
+            segments: vec![],
+            points,
+        }
+    }
+
+    /// The picture as an SVG document
+    pub fn to_svg(&self) -> String {
+        let (x_min, x_max, y_min, y_max) = self.view;
+        let pixel = |(x, y): (f64, f64)| ((x - x_min) / (x_max - x_min) * WIDTH, (y_max - y) / (y_max - y_min) * HEIGHT);
+
+        let mut svg = String::new();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#, WIDTH, HEIGHT, WIDTH, HEIGHT).unwrap();
+        writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+        // The axes, if they are in the window
+        let mut axes = vec![];
+        if x_min <= 0.0 && 0.0 <= x_max {
+            axes.push(((0.0, y_min), (0.0, y_max)));
+        }
+        if y_min <= 0.0 && 0.0 <= y_max {
+            axes.push(((x_min, 0.0), (x_max, 0.0)));
+        }
+        for (from, to) in axes {
+            let (from, to) = (pixel(from), pixel(to));
+            writeln!(svg, r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="gray"/>"#, from.0, from.1, to.0, to.1).unwrap();
+        }
+
+        for curve in self.curves.iter() {
+            let path: Vec<String> = curve.iter().map(|&point| pixel(point)).map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+            writeln!(svg, r#"<polyline points="{}" fill="none" stroke="steelblue" stroke-width="2"/>"#, path.join(" ")).unwrap();
+        }
+
+        for segment in self.segments.iter() {
+            let (from, to) = (pixel(segment.from), pixel(segment.to));
+            let dash = if segment.dashed { r#" stroke-dasharray="6 4""# } else { "" };
+            writeln!(svg, r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="firebrick"{}/>"#, from.0, from.1, to.0, to.1, dash).unwrap();
+        }
+
+        // Scatter plots get small dots, the points of an addition bigger ones with a label
+        let radius = if self.curves.is_empty() { 2.0 } else { 4.0 };
+        for (point, label) in self.points.iter() {
+            let (x, y) = pixel(*point);
+            writeln!(svg, r#"<circle cx="{:.2}" cy="{:.2}" r="{}" fill="black"/>"#, x, y, radius).unwrap();
+            if !label.is_empty() {
+                writeln!(svg, r#"<text x="{:.2}" y="{:.2}" font-family="sans-serif" font-size="14">{}</text>"#, x + 6.0, y - 6.0, label).unwrap();
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The picture as CSV rows of `series,x,y,label`: the samples of each piece of the
+    /// curve, the ends of the lines and the points
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("series,x,y,label\n");
+        for (i, curve) in self.curves.iter().enumerate() {
+            for (x, y) in curve {
+                writeln!(csv, "curve {},{},{},", i, x, y).unwrap();
+            }
+        }
+        for (i, segment) in self.segments.iter().enumerate() {
+            for (x, y) in [segment.from, segment.to] {
+                writeln!(csv, "line {},{},{},", i, x, y).unwrap();
+            }
+        }
+        for ((x, y), label) in self.points.iter() {
+            writeln!(csv, "point,{},{},{}", x, y, label).unwrap();
+        }
+        csv
+    }
+
+    /// Writes the picture to `path`, as SVG or CSV according to its extension
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let contents = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("svg") => self.to_svg(),
+            Some("csv") => self.to_csv(),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Plots are saved as .svg or .csv")),
+        };
+        std::fs::write(path, contents)
+    }
+
+    /// The curve in a window that holds its real roots and the labelled points
+    fn with_real_curve(curve: &Curve<Rational>, labelled: &[((f64, f64), String)]) -> Plot {
+        let (a, b) = (curve.a().to_f64(), curve.b().to_f64());
+        let cubic = |x: f64| x * x * x + a * x + b;
+        let roots = real_roots(cubic, 1.0 + a.abs().max(b.abs()));
+
+        // x from a bit left of the leftmost root or point to a bit right of the rightmost one
+        let xs = roots.iter().copied().chain(labelled.iter().map(|((x, _), _)| *x));
+        let (low, high) = xs.fold((f64::MAX, f64::MIN), |(low, high), x| (low.min(x), high.max(x)));
+        let margin = (high - low) * 0.25 + 1.0;
+        let (x_min, x_max) = (low - margin, high + margin);
+
+        // Pieces of the curve between the roots, where the cubic is not negative
+        let mut curves: Vec<Vec<(f64, f64)>> = vec![];
+        let mut boundaries = vec![x_min];
+        boundaries.extend(roots.iter().copied().filter(|&root| x_min < root && root < x_max));
+        boundaries.push(x_max);
+        for interval in boundaries.windows(2) {
+            let (start, end) = (interval[0], interval[1]);
+            if cubic((start + end) / 2.0) < 0.0 {
+                continue;
+            }
+
+            let steps = ((end - start) / (x_max - x_min) * SAMPLES as f64).ceil().max(2.0) as usize;
+            let upper: Vec<(f64, f64)> = (0..=steps)
+                .map(|i| start + (end - start) * i as f64 / steps as f64)
+                .map(|x| (x, cubic(x).max(0.0).sqrt()))
+                .collect();
+            let lower = upper.iter().rev().map(|&(x, y)| (x, -y));
+            curves.push(upper.iter().copied().chain(lower).collect());
+        }
+
+        // y symmetric around the x axis, with room for the curve and the points
+        let heights = curves.iter().flatten().map(|(_, y)| y.abs()).chain(labelled.iter().map(|((_, y), _)| y.abs()));
+        let y_max = heights.fold(1.0, f64::max) * 1.1;
+
+        Plot {
+            view: (x_min, x_max, -y_max, y_max),
+            curves,
+            segments: vec![],
+            points: labelled.to_vec(),
+        }
+    }
+}
+
+/// The affine coordinates of a point over the rationals, rounded to floats
+fn coordinates(point: &EllipticPoint) -> (f64, f64) {
+    match (point.x(), point.y()) {
+        (Some(x), Some(y)) => (x.to_f64(), y.to_f64()),
+        _ => panic!("The point at infinity cannot be drawn"),
+    }
+}
+
+/// The real roots of a cubic within (-bound, bound), by sign changes and bisection
+fn real_roots(cubic: impl Fn(f64) -> f64, bound: f64) -> Vec<f64> {
+    let step = 2.0 * bound / SAMPLES as f64;
+    let mut roots = vec![];
+    for i in 0..SAMPLES {
+        let (mut low, mut high) = (-bound + step * i as f64, -bound + step * (i + 1) as f64);
+        if cubic(low) == 0.0 {
+            roots.push(low);
+            continue;
+        }
+        // A root at the end of the interval is the start of the next one
+        if cubic(high) == 0.0 || cubic(low).signum() == cubic(high).signum() {
+            continue;
+        }
+
+        for _ in 0..60 {
+            let middle = (low + high) / 2.0;
+            if cubic(low).signum() == cubic(middle).signum() {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        roots.push((low + high) / 2.0);
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use ec_cryptography::{Curve, Point};
+    use finite_fields::PrimeField;
+    use rug::{Integer, Rational};
+
+    use super::{real_roots, Plot};
+
+    fn labels(plot: &Plot) -> Vec<(&str, (f64, f64))> {
+        plot.points.iter().map(|(point, label)| (label.as_str(), *point)).collect()
+    }
+
+    fn point(x: i32, y: i32) -> Point<Rational> {
+        Point::new(Some(Rational::from(x)), Some(Rational::from(y)), Rational::from(5), Rational::from(7))
+    }
+
+    #[test]
+    fn test_real_roots() {
+        // x^3 - x = x(x - 1)(x + 1)
+        let roots = real_roots(|x| x * x * x - x, 2.0);
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip([-1.0, 0.0, 1.0]) {
+            assert!((root - expected).abs() < 1e-9);
+        }
+        assert_eq!(real_roots(|x| x * x * x + 5.0 * x + 7.0, 8.0).len(), 1);
+    }
+
+    #[test]
+    fn test_addition() {
+        // (2, 5) + (-1, -1) = (3, -7) on y^2 = x^3 + 5x + 7
+        let plot = Plot::addition(&point(2, 5), &point(-1, -1));
+        assert_eq!(
+            labels(&plot),
+            vec![("P", (2.0, 5.0)), ("Q", (-1.0, -1.0)), ("-R", (3.0, 7.0)), ("R = P + Q", (3.0, -7.0))]
+        );
+        assert_eq!(plot.curves.len(), 1);
+        assert_eq!(plot.segments.len(), 2);
+
+        let svg = plot.to_svg();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(svg.contains("stroke-dasharray"));
+
+        // Doubling draws the tangent, a vertical chord has no third point
+        assert_eq!(labels(&Plot::addition(&point(2, 5), &point(2, 5)))[0].0, "P = Q");
+        let vertical = Plot::addition(&point(2, 5), &point(2, -5));
+        assert_eq!(labels(&vertical).len(), 2);
+        assert_eq!(vertical.segments.len(), 1);
+    }
+
+    #[test]
+    fn test_two_pieces() {
+        // y^2 = x^3 - x has an oval between -1 and 0
+        let curve = Curve::new(Rational::from(-1), Rational::from(0));
+        assert_eq!(Plot::real_curve(&curve).curves.len(), 2);
+    }
+
+    #[test]
+    fn test_field_points() {
+        let field = PrimeField::new(Integer::from(223)).unwrap();
+        let curve = Curve::new(field.zero(), field.element(Integer::from(7)));
+        let plot = Plot::field_points(&curve);
+
+        // Every point but the point at infinity
+        let count = curve.points().len() - 1;
+        assert_eq!(plot.to_csv().lines().count(), count + 1);
+        assert_eq!(plot.to_svg().matches("<circle").count(), count);
+        assert!(plot.to_csv().contains("point,47,71,"));
+    }
+
+    #[test]
+    fn test_save() {
+        let plot = Plot::addition(&point(2, 5), &point(-1, -1));
+        // The process id keeps concurrent test runs from sharing the file
+        let name = format!("elliptic_curve_test_save_{}", std::process::id());
+        let directory = std::env::temp_dir();
+
+        let path = directory.join(format!("{}.svg", name));
+        plot.save(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), plot.to_svg());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(plot.save(directory.join(format!("{}.png", name))).is_err());
+    }
+}
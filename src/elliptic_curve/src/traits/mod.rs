@@ -1,3 +1,6 @@
-pub trait Serializer {
-    fn sec(&self) -> Vec<u8>;
-}
\ No newline at end of file
+// `EllipticPoint` does not implement `Serializer`. The SEC encoding pads x and y to the byte
+// length of a prime field and takes the parity of y for the compressed form, and neither
+// exists over the rationals: a coordinate like 287/1000 has no fixed-width encoding and no
+// parity. The trait is re-exported so that code using both crates imports it from one place,
+// the points over finite fields of `ec_cryptography` implement it.
+pub use ec_cryptography::traits::Serializer;
@@ -0,0 +1,106 @@
+use crate::{FieldElement, FieldError, PrimeField};
+
+/// Inverts every element of the slice in place, using a single field inversion.
+///
+/// This is Montgomery's trick: with the prefix products a0, a0*a1, ..., a0*...*an, the
+/// inverse of the full product is enough to recover each individual inverse with two
+/// multiplications. When an element is zero the slice is left untouched and the indices
+/// of all the zero elements are returned in `FieldError::NonInvertible`.
+pub fn batch_invert(elements: &mut [FieldElement]) -> Result<(), FieldError> {
+    let first = match elements.first() {
+        Some(first) => first.clone(),
+        None => return Ok(()),
+    };
+
+    if let Some(other) = elements.iter().find(|element| !PrimeField::is_same(first.field(), element.field())) {
+        return Err(FieldError::MismatchedModuli(first.order(), other.order()));
+    }
+
+    let zeros: Vec<usize> = elements.iter()
+        .enumerate()
+        .filter(|(_, element)| element.is_zero())
+        .map(|(index, _)| index)
+        .collect();
+    if !zeros.is_empty() {
+        return Err(FieldError::NonInvertible(zeros));
+    }
+
+    // prefix_products[i] = elements[0] * ... * elements[i]
+    let mut prefix_products = Vec::with_capacity(elements.len());
+    let mut product = first.field().one();
+    for element in elements.iter() {
+        product *= element;
+        prefix_products.push(product.clone());
+    }
+
+    // The only inversion, the product of all the elements
+    let mut inverse = first.field().one().checked_div(&product)?;
+
+    for i in (1..elements.len()).rev() {
+        // inverse is (elements[0] * ... * elements[i])^-1 at this point
+        let element_inverse = &inverse * &prefix_products[i - 1];
+        inverse *= &elements[i];
+        elements[i] = element_inverse;
+    }
+    elements[0] = inverse;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::Integer;
+
+    use crate::{FieldElement, FieldError, PrimeField};
+
+    use super::batch_invert;
+
+    #[test]
+    fn test_batch_invert() {
+        let field = PrimeField::new(Integer::from(223)).unwrap();
+        let originals: Vec<FieldElement> = (1..223).map(|num| field.element(Integer::from(num))).collect();
+
+        let mut elements = originals.clone();
+        batch_invert(&mut elements).unwrap();
+
+        for (original, inverse) in originals.iter().zip(elements.iter()) {
+            assert_eq!(original * inverse, field.one());
+            assert_eq!(*inverse, field.one().checked_div(original).unwrap());
+        }
+
+        let mut single = vec![field.element(Integer::from(3))];
+        batch_invert(&mut single).unwrap();
+        assert_eq!(single, vec![field.element(Integer::from(149))]);
+
+        let mut empty: Vec<FieldElement> = vec![];
+        assert_eq!(batch_invert(&mut empty), Ok(()));
+    }
+
+    #[test]
+    fn test_batch_invert_zero() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let originals = vec![
+            field.zero(),
+            field.element(Integer::from(4)),
+            field.element(Integer::from(31)),
+            field.element(Integer::from(17)),
+        ];
+
+        let mut elements = originals.clone();
+        assert_eq!(batch_invert(&mut elements), Err(FieldError::NonInvertible(vec![0, 2])));
+        assert_eq!(elements, originals, "Elements should be left untouched");
+    }
+
+    #[test]
+    fn test_batch_invert_mismatched() {
+        let mut elements = vec![
+            FieldElement::new(Integer::from(3), Integer::from(31)),
+            FieldElement::new(Integer::from(3), Integer::from(29)),
+        ];
+
+        assert_eq!(
+            batch_invert(&mut elements),
+            Err(FieldError::MismatchedModuli(Integer::from(31), Integer::from(29)))
+        );
+    }
+}
@@ -0,0 +1,278 @@
+// Constant-time arithmetic for secret operands, such as private keys and nonces.
+//
+// Nothing here branches on secret data or uses it as an index: conditions are carried as
+// all-zeros / all-ones masks (`Choice`), and the fixed-width operations always do the same
+// amount of work, down to the number of reduction steps. Fe256 and Scalar expose it through
+// their `ct_*` methods and the `ConstantTime` trait. The regular operators are faster but
+// their timing depends on the operands.
+
+use std::{hint::black_box, ops::{BitAnd, BitOr, Not}};
+
+use crate::limbs::{self, adc, mac, Limbs};
+
+/// A secret boolean, stored as a mask: all zeros for false and all ones for true
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Choice(u64);
+
+impl Choice {
+    pub const FALSE: Choice = Choice(0);
+    pub const TRUE: Choice = Choice(u64::MAX);
+
+    /// Converts a bit, only the lowest bit of `bit` is used
+    pub fn from_bit(bit: u64) -> Choice {
+        // black_box keeps the compiler from turning the mask back into a branch
+        Choice(black_box(bit & 1).wrapping_neg())
+    }
+
+    /// Reveals the value. Only for results that may become public, e.g. whether a signature is valid.
+    pub fn declassify(self) -> bool {
+        self.0 != 0
+    }
+
+    fn mask(self) -> u64 {
+        self.0
+    }
+}
+
+impl Not for Choice {
+    type Output = Choice;
+
+    fn not(self) -> Choice {
+        Choice(!self.0)
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Choice;
+
+    fn bitand(self, other: Choice) -> Choice {
+        Choice(self.0 & other.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Choice;
+
+    fn bitor(self, other: Choice) -> Choice {
+        Choice(self.0 | other.0)
+    }
+}
+
+/// Comparison and selection that take the same time whatever the values
+pub trait ConstantTime: Sized + Copy {
+    fn ct_eq(&self, other: &Self) -> Choice;
+
+    /// `b` if `choice` is true, `a` otherwise
+    fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Swaps `a` and `b` if `choice` is true
+    fn ct_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let (first, second) = (Self::ct_select(a, b, choice), Self::ct_select(b, a, choice));
+        *a = first;
+        *b = second;
+    }
+}
+
+/// True if `word` is not zero
+fn is_nonzero(word: u64) -> Choice {
+    Choice::from_bit((word | word.wrapping_neg()) >> 63)
+}
+
+pub(crate) fn eq(a: &Limbs, b: &Limbs) -> Choice {
+    let difference = a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y));
+    !is_nonzero(difference)
+}
+
+pub(crate) fn select(a: &Limbs, b: &Limbs, choice: Choice) -> Limbs {
+    let mask = choice.mask();
+    std::array::from_fn(|i| a[i] ^ (mask & (a[i] ^ b[i])))
+}
+
+/// a - m if a (with bit 256 set to `carry`) is at least m, a otherwise. a must be below 2m.
+fn reduce_once(a: &Limbs, carry: u64, modulus: &Limbs) -> Limbs {
+    let (difference, borrow) = limbs::sub(a, modulus);
+    select(a, &difference, Choice::from_bit(carry) | !Choice::from_bit(borrow))
+}
+
+pub(crate) fn add_mod(a: &Limbs, b: &Limbs, modulus: &Limbs) -> Limbs {
+    let (sum, carry) = limbs::add(a, b);
+    reduce_once(&sum, carry, modulus)
+}
+
+pub(crate) fn sub_mod(a: &Limbs, b: &Limbs, modulus: &Limbs) -> Limbs {
+    let (difference, borrow) = limbs::sub(a, b);
+    let wrapped = limbs::add(&difference, modulus).0;
+    select(&difference, &wrapped, Choice::from_bit(borrow))
+}
+
+/// `limbs::reduce_wide` with a fixed number of folds.
+///
+/// Each fold replaces hi * 2^256 + lo by hi * complement + lo. For a complement of at most
+/// 129 bits (the secp256k1 n, p only needs 33) the high half is at most 130, 4, 1 and 0 bits
+/// long after one, two, three and four folds, and the result is then below 2m.
+fn reduce_wide(mut wide: [u64; 8], complement: &[u64], modulus: &Limbs) -> Limbs {
+    for _ in 0..4 {
+        let mut next = [0u64; 8];
+        next[..4].copy_from_slice(&wide[..4]);
+
+        for (i, &high) in wide[4..].iter().enumerate() {
+            let mut carry = 0;
+            for (j, &c) in complement.iter().enumerate() {
+                (next[i + j], carry) = mac(next[i + j], high, c, carry);
+            }
+            for limb in next[i + complement.len()..].iter_mut() {
+                (*limb, carry) = adc(*limb, carry, 0);
+            }
+        }
+
+        wide = next;
+    }
+
+    reduce_once(&[wide[0], wide[1], wide[2], wide[3]], 0, modulus)
+}
+
+pub(crate) fn mul_mod(a: &Limbs, b: &Limbs, complement: &[u64], modulus: &Limbs) -> Limbs {
+    reduce_wide(limbs::mul_wide(a, b), complement, modulus)
+}
+
+/// base^exponent, squaring and multiplying for all 256 bits of the exponent and keeping
+/// the product with a mask, so neither the base nor the exponent affect the timing
+pub(crate) fn pow_mod(base: &Limbs, exponent: &Limbs, complement: &[u64], modulus: &Limbs) -> Limbs {
+    let mut result = [1, 0, 0, 0];
+    for i in (0..256).rev() {
+        result = mul_mod(&result, &result, complement, modulus);
+        let product = mul_mod(&result, base, complement, modulus);
+
+        let bit = exponent[i / 64] >> (i % 64);
+        result = select(&result, &product, Choice::from_bit(bit));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{hint::black_box, time::Instant};
+
+    use rand::RngCore;
+    use rug::Integer;
+
+    use crate::{rng::{self, DeterministicRng}, Fe256, Scalar};
+
+    use super::{Choice, ConstantTime};
+
+    #[test]
+    fn test_choice() {
+        assert!(Choice::from_bit(1).declassify());
+        assert!(!Choice::from_bit(2).declassify());
+        assert_eq!(!Choice::TRUE, Choice::FALSE);
+        assert_eq!(Choice::TRUE & Choice::FALSE, Choice::FALSE);
+        assert_eq!(Choice::TRUE | Choice::FALSE, Choice::TRUE);
+    }
+
+    #[test]
+    fn test_matches_variable_time() {
+        let mut rng = rng::deterministic(3);
+        let edge_cases = [Fe256::ZERO, Fe256::ONE, -Fe256::ONE, Fe256::from_integer(&(Fe256::modulus() >> 1))];
+
+        let values: Vec<Fe256> = edge_cases.into_iter().chain((0..20).map(|_| Fe256::random(&mut rng))).collect();
+        for a in values.iter() {
+            for b in values.iter() {
+                assert_eq!(a.ct_add(b), a + b);
+                assert_eq!(a.ct_sub(b), a - b);
+                assert_eq!(a.ct_mul(b), a * b);
+                assert_eq!(a.ct_eq(b).declassify(), a == b);
+                assert_eq!(Fe256::ct_select(a, b, Choice::TRUE), *b);
+                assert_eq!(Fe256::ct_select(a, b, Choice::FALSE), *a);
+            }
+
+            assert_eq!(a.ct_invert(), a.invert().unwrap_or(Fe256::ZERO));
+        }
+
+        let scalars: Vec<Scalar> = (0..20).map(|_| Scalar::random(&mut rng)).chain([Scalar::ZERO, -Scalar::ONE]).collect();
+        for a in scalars.iter() {
+            for b in scalars.iter() {
+                assert_eq!(a.ct_add(b), a + b);
+                assert_eq!(a.ct_sub(b), a - b);
+                assert_eq!(a.ct_mul(b), a * b);
+            }
+
+            assert_eq!(a.ct_invert(), a.invert().unwrap_or(Scalar::ZERO));
+        }
+
+        // The largest possible product, (n - 1)^2, needs all four folds
+        let largest = -Scalar::ONE;
+        assert_eq!(largest.ct_mul(&largest).to_integer(), Integer::from(1));
+    }
+
+    #[test]
+    fn test_swap() {
+        let (mut a, mut b) = (Fe256::from_u64(1), Fe256::from_u64(2));
+
+        Fe256::ct_swap(&mut a, &mut b, Choice::FALSE);
+        assert_eq!((a, b), (Fe256::from_u64(1), Fe256::from_u64(2)));
+
+        Fe256::ct_swap(&mut a, &mut b, Choice::TRUE);
+        assert_eq!((a, b), (Fe256::from_u64(2), Fe256::from_u64(1)));
+    }
+
+    // Welch's t statistic between two samples
+    fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+        let mean = |xs: &[f64]| xs.iter().sum::<f64>() / xs.len() as f64;
+        let variance = |xs: &[f64], m: f64| xs.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (xs.len() - 1) as f64;
+
+        let (mean_a, mean_b) = (mean(a), mean(b));
+        (mean_a - mean_b) / (variance(a, mean_a) / a.len() as f64 + variance(b, mean_b) / b.len() as f64).sqrt()
+    }
+
+    // dudect: times `operation` on one fixed input and on random inputs, interleaved at
+    // random, and returns the t statistic between the two timing distributions. The slowest
+    // measurements (interrupts, context switches) are dropped first.
+    fn leakage<T: Copy>(fixed: T, random: impl Fn(&mut DeterministicRng) -> T, operation: impl Fn(T) -> T) -> f64 {
+        const SAMPLES: usize = 20_000;
+
+        let mut rng = rng::deterministic(0);
+        let inputs: Vec<(usize, T)> = (0..SAMPLES)
+            .map(|_| match rng.next_u32() & 1 {
+                0 => (0, fixed),
+                _ => (1, random(&mut rng)),
+            })
+            .collect();
+
+        let mut timings = Vec::with_capacity(SAMPLES);
+        for (class, input) in inputs {
+            let start = Instant::now();
+            black_box(operation(black_box(input)));
+            timings.push((class, start.elapsed().as_nanos() as f64));
+        }
+
+        let mut sorted: Vec<f64> = timings.iter().map(|&(_, time)| time).collect();
+        sorted.sort_by(f64::total_cmp);
+        let cutoff = sorted[SAMPLES * 9 / 10];
+
+        let class = |wanted: usize| -> Vec<f64> {
+            timings.iter().filter(|&&(class, time)| class == wanted && time <= cutoff).map(|&(_, time)| time).collect()
+        };
+        welch_t(&class(0), &class(1))
+    }
+
+    #[test]
+    #[ignore = "statistical timing test, run with `cargo test --release -p finite_fields -- --ignored`"]
+    fn test_dudect() {
+        // A |t| above 4.5 is the usual dudect threshold for a timing leak
+        const THRESHOLD: f64 = 4.5;
+
+        let invert = leakage(Fe256::ONE, Fe256::random, |a| a.ct_invert());
+        let multiply = leakage(Scalar::ONE, Scalar::random, |a| a.ct_mul(&a));
+        let select = leakage(Choice::FALSE, |_| Choice::TRUE, |choice| {
+            Fe256::ct_select(&Fe256::ONE, &-Fe256::ONE, choice);
+            choice
+        });
+        // For comparison, the binary GCD returns immediately for one
+        let variable_time = leakage(Fe256::ONE, Fe256::random, |a| a.invert().unwrap());
+
+        println!("t statistics: ct_invert {:.2}, ct_mul {:.2}, ct_select {:.2}, invert {:.2}", invert, multiply, select, variable_time);
+        assert!(invert.abs() < THRESHOLD, "ct_invert leaks timing, t = {:.2}", invert);
+        assert!(multiply.abs() < THRESHOLD, "ct_mul leaks timing, t = {:.2}", multiply);
+        assert!(select.abs() < THRESHOLD, "ct_select leaks timing, t = {:.2}", select);
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter};
+
+use rug::Integer;
+
+/// Errors returned by the fallible FieldElement API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    /// The modulus of the field is not a prime number
+    NonPrimeModulus(Integer),
+    /// The operands belong to fields with different moduli
+    MismatchedModuli(Integer, Integer),
+    /// The divisor (or the base of a negative power) is zero
+    DivisionByZero,
+    /// Elements at these indices are zero and have no inverse
+    NonInvertible(Vec<usize>),
+    /// The polynomial given as the modulus of an extension field is not irreducible
+    ReducibleModulus(Vec<Integer>),
+    /// The operands belong to different extension fields
+    MismatchedExtensions,
+    /// An encoded element has the wrong number of bytes, the expected and the actual length
+    InvalidLength(usize, usize),
+    /// A string that is not made of pairs of hex digits
+    InvalidHex(String),
+    /// An encoded number is not below the modulus
+    UnreducedValue(Integer, Integer),
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            FieldError::NonPrimeModulus(prime) => write!(f, "Number must be prime, got {}", prime),
+            FieldError::MismatchedModuli(left, right) => {
+                write!(f, "Primes must be equal, got {} and {}", left, right)
+            }
+            FieldError::DivisionByZero => write!(f, "Division by zero"),
+            FieldError::NonInvertible(indices) => write!(f, "Elements at {:?} are zero and cannot be inverted", indices),
+            FieldError::ReducibleModulus(coeffs) => write!(f, "Polynomial {:?} is not irreducible", coeffs),
+            FieldError::MismatchedExtensions => write!(f, "Elements must belong to the same extension field"),
+            FieldError::InvalidLength(expected, actual) => write!(f, "Expected {} bytes, got {}", expected, actual),
+            FieldError::InvalidHex(hex) => write!(f, "Invalid hex string {:?}", hex),
+            FieldError::UnreducedValue(num, prime) => write!(f, "Value {} is not below the modulus {}", num, prime),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
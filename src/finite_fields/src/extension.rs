@@ -0,0 +1,635 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rand::{CryptoRng, RngCore};
+use rug::{ops::Pow, Integer};
+
+use crate::{
+    ops::{impl_binary_op, impl_neg, impl_scalar_op, impl_scalar_ops_reversed},
+    Field, FieldElement, FieldError, PrimeField,
+};
+
+/// A polynomial over GF(p), coefficients from the lowest degree up and without
+/// trailing zeros, so the zero polynomial is empty
+type Polynomial = Vec<FieldElement>;
+
+/// The extension field GF(p^k) = GF(p)[x] / (f), for a monic irreducible f of degree k.
+///
+/// Like PrimeField, the modulus is checked once when the field is created and shared
+/// between the elements through an `Arc`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExtField {
+    base: Arc<PrimeField>,
+    modulus: Polynomial,
+}
+
+impl ExtField {
+    /// Creates GF(p^k) from the coefficients of f, lowest degree first.
+    /// f is made monic, an error is returned if it is constant or not irreducible.
+    pub fn new(base: &Arc<PrimeField>, modulus: Vec<Integer>) -> Result<Arc<ExtField>, FieldError> {
+        let polynomial = trim(modulus.iter().map(|num| base.element(num.clone())).collect());
+        if polynomial.len() < 2 {
+            return Err(FieldError::ReducibleModulus(modulus));
+        }
+
+        let lead_inverse = base.one() / polynomial.last().unwrap();
+        let polynomial: Polynomial = polynomial.into_iter().map(|coeff| coeff * &lead_inverse).collect();
+        if !is_irreducible(&polynomial) {
+            return Err(FieldError::ReducibleModulus(modulus));
+        }
+
+        Ok(Arc::new(ExtField { base: Arc::clone(base), modulus: polynomial }))
+    }
+
+    /// Creates GF(p^degree) with the modulus from `find_irreducible`
+    pub fn with_degree(base: &Arc<PrimeField>, degree: usize) -> Arc<ExtField> {
+        let modulus = find_irreducible(base, degree);
+        Arc::new(ExtField { base: Arc::clone(base), modulus })
+    }
+
+    /// The prime field GF(p) the extension is built on
+    pub fn base(&self) -> &Arc<PrimeField> {
+        &self.base
+    }
+
+    pub fn degree(&self) -> usize {
+        self.modulus.len() - 1
+    }
+
+    /// The coefficients of the monic modulus f, lowest degree first
+    pub fn modulus(&self) -> &[FieldElement] {
+        &self.modulus
+    }
+
+    /// The number of elements, p^k
+    pub fn order(&self) -> Integer {
+        self.base.prime().clone().pow(self.degree() as u32)
+    }
+
+    /// Creates the element c0 + c1*x + c2*x^2 + ..., reduced modulo f
+    pub fn element(self: &Arc<Self>, coeffs: Vec<Integer>) -> ExtFieldElement {
+        let polynomial = trim(coeffs.into_iter().map(|num| self.base.element(num)).collect());
+        self.with_polynomial(divide(&polynomial, &self.modulus).1)
+    }
+
+    /// Embeds an element of the base field
+    pub fn from_base(self: &Arc<Self>, element: &FieldElement) -> ExtFieldElement {
+        self.element(vec![element.num()])
+    }
+
+    pub fn zero(self: &Arc<Self>) -> ExtFieldElement {
+        self.with_polynomial(vec![])
+    }
+
+    pub fn one(self: &Arc<Self>) -> ExtFieldElement {
+        self.element(vec![Integer::from(1)])
+    }
+
+    /// The class of x, a root of the modulus f
+    pub fn x(self: &Arc<Self>) -> ExtFieldElement {
+        self.element(vec![Integer::ZERO, Integer::from(1)])
+    }
+
+    /// Two extension fields are the same if they share the same context, or failing that,
+    /// the same prime and the same irreducible polynomial. GF(p^k) built from two different
+    /// polynomials are isomorphic, but their elements are not comparable coefficient-wise.
+    pub fn is_same(this: &Arc<Self>, other: &Arc<Self>) -> bool {
+        Arc::ptr_eq(this, other) || this == other
+    }
+
+    fn with_polynomial(self: &Arc<Self>, coeffs: Polynomial) -> ExtFieldElement {
+        ExtFieldElement { coeffs, field: Arc::clone(self) }
+    }
+}
+
+/// An element of GF(p^k), a polynomial of degree below k over GF(p)
+#[derive(Clone)]
+pub struct ExtFieldElement {
+    coeffs: Polynomial,
+    field: Arc<ExtField>,
+}
+
+impl ExtFieldElement {
+    /// The k coefficients, lowest degree first
+    pub fn coefficients(&self) -> Vec<FieldElement> {
+        let mut coeffs = self.coeffs.clone();
+        coeffs.resize(self.field.degree(), self.field.base.zero());
+        coeffs
+    }
+
+    pub fn field(&self) -> &Arc<ExtField> {
+        &self.field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The multiplicative inverse, or None for zero.
+    /// Found with the extended Euclidean algorithm on self and the modulus.
+    pub fn inv(&self) -> Option<ExtFieldElement> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Invariant: r = s * self (mod f)
+        let (mut r0, mut r1) = (self.field.modulus.clone(), self.coeffs.clone());
+        let (mut s0, mut s1) = (vec![], vec![self.field.base.one()]);
+        while !r1.is_empty() {
+            let (quotient, remainder) = divide(&r0, &r1);
+            let s = subtract(&s0, &multiply(&quotient, &s1));
+
+            (r0, r1) = (r1, remainder);
+            (s0, s1) = (s1, s);
+        }
+
+        // f is irreducible, so the gcd r0 is a non-zero constant
+        let scale = vec![self.field.base.one() / &r0[0]];
+        Some(self.field.with_polynomial(divide(&multiply(&s0, &scale), &self.field.modulus).1))
+    }
+
+    pub fn pow(&self, exponent: Integer) -> Result<ExtFieldElement, FieldError> {
+        let base = if exponent < 0 {
+            self.inv().ok_or(FieldError::DivisionByZero)?
+        } else {
+            self.clone()
+        };
+
+        let exponent = exponent.abs();
+        let mut result = self.field.one();
+        for i in (0..exponent.significant_bits()).rev() {
+            result *= result.clone();
+            if exponent.get_bit(i) {
+                result *= &base;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The Frobenius automorphism a -> a^p, it fixes exactly the base field
+    pub fn frobenius(&self) -> ExtFieldElement {
+        self.pow(self.field.base.prime().clone()).unwrap()
+    }
+
+    /// A square root of self, or None if self is not a square
+    pub fn sqrt(&self) -> Option<ExtFieldElement> {
+        if self.is_zero() {
+            return Some(self.clone());
+        }
+
+        let order = self.field.order();
+        // Squaring is a bijection in characteristic 2, the root is a^(q/2)
+        if order.is_even() {
+            return self.pow(order >> 1).ok();
+        }
+
+        let one = self.field.one();
+        let q_minus_one = order.clone() - 1u32;
+        if self.pow(q_minus_one.clone() >> 1).unwrap() != one {
+            return None;
+        }
+
+        if order.mod_u(4) == 3 {
+            return self.pow((order + 1u32) >> 2).ok();
+        }
+
+        // Tonelli-Shanks, with q - 1 = t * 2^s and t odd
+        let s = q_minus_one.find_one(0).unwrap();
+        let t = q_minus_one.clone() >> s;
+
+        // Any non-square z will do, the elements x + c cover the whole field when k = 1
+        let mut c = Integer::ZERO;
+        let mut z = self.field.x();
+        while z.is_zero() || z.pow(q_minus_one.clone() >> 1).unwrap() == one {
+            c += 1;
+            z = self.field.element(vec![c.clone(), Integer::from(1)]);
+        }
+
+        let mut m = s;
+        let mut b = z.pow(t.clone()).unwrap();
+        let mut power = self.pow(t.clone()).unwrap();
+        let mut root = self.pow((t + 1u32) >> 1).unwrap();
+
+        // Invariant: root^2 = self * power, where power has order 2^i for some i < m
+        while power != one {
+            let mut i = 0;
+            let mut square = power.clone();
+            while square != one {
+                square *= square.clone();
+                i += 1;
+            }
+
+            let mut factor = b.clone();
+            for _ in 0..(m - i - 1) {
+                factor *= factor.clone();
+            }
+
+            m = i;
+            b = &factor * &factor;
+            power *= &b;
+            root *= factor;
+        }
+
+        Some(root)
+    }
+
+    pub fn checked_add(&self, other: &ExtFieldElement) -> Result<ExtFieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        Ok(self.field.with_polynomial(add(&self.coeffs, &other.coeffs)))
+    }
+
+    pub fn checked_sub(&self, other: &ExtFieldElement) -> Result<ExtFieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        Ok(self.field.with_polynomial(subtract(&self.coeffs, &other.coeffs)))
+    }
+
+    pub fn checked_mul(&self, other: &ExtFieldElement) -> Result<ExtFieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        let product = multiply(&self.coeffs, &other.coeffs);
+        Ok(self.field.with_polynomial(divide(&product, &self.field.modulus).1))
+    }
+
+    pub fn checked_div(&self, other: &ExtFieldElement) -> Result<ExtFieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        let inverse = other.inv().ok_or(FieldError::DivisionByZero)?;
+        self.checked_mul(&inverse)
+    }
+
+    fn ensure_same_field(&self, other: &ExtFieldElement) -> Result<(), FieldError> {
+        if !ExtField::is_same(&self.field, &other.field) {
+            return Err(FieldError::MismatchedExtensions);
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ExtFieldElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let terms: Vec<String> = self.coefficients()
+            .iter()
+            .enumerate()
+            .map(|(degree, coeff)| match degree {
+                0 => coeff.num().to_string(),
+                1 => format!("{}x", coeff.num()),
+                _ => format!("{}x^{}", coeff.num(), degree),
+            })
+            .collect();
+
+        write!(f, "ExtFieldElement_{}^{}({})", self.field.base.prime(), self.field.degree(), terms.join(" + "))
+    }
+}
+
+impl PartialEq for ExtFieldElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.coeffs == other.coeffs && ExtField::is_same(&self.field, &other.field)
+    }
+}
+
+impl Eq for ExtFieldElement {}
+
+// The operators panic on invalid operands, the checked_* methods are the fallible equivalents
+
+impl_binary_op!(ExtFieldElement, Add, add, AddAssign, add_assign, |a, b| {
+    a.checked_add(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(ExtFieldElement, Sub, sub, SubAssign, sub_assign, |a, b| {
+    a.checked_sub(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(ExtFieldElement, Mul, mul, MulAssign, mul_assign, |a, b| {
+    a.checked_mul(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(ExtFieldElement, Div, div, DivAssign, div_assign, |a, b| {
+    a.checked_div(b).unwrap_or_else(|err| panic!("{}", err))
+});
+
+impl_neg!(ExtFieldElement, |a| a.field.with_polynomial(a.coeffs.iter().map(|coeff| -coeff).collect()));
+
+impl_scalar_op!(ExtFieldElement, u64, Add, add, AddAssign, add_assign, |a, n| a + a.field.element(vec![Integer::from(n)]));
+impl_scalar_op!(ExtFieldElement, u64, Sub, sub, SubAssign, sub_assign, |a, n| a - a.field.element(vec![Integer::from(n)]));
+impl_scalar_op!(ExtFieldElement, u64, Mul, mul, MulAssign, mul_assign, |a, n| {
+    a.field.with_polynomial(trim(a.coeffs.iter().map(|coeff| coeff * n).collect()))
+});
+impl_scalar_ops_reversed!(ExtFieldElement, u64);
+
+impl Field for ExtFieldElement {
+    fn zero(&self) -> ExtFieldElement {
+        self.field.zero()
+    }
+
+    fn one(&self) -> ExtFieldElement {
+        self.field.one()
+    }
+
+    fn element(&self, num: &Integer) -> ExtFieldElement {
+        self.field.element(vec![num.clone()])
+    }
+
+    fn is_zero(&self) -> bool {
+        ExtFieldElement::is_zero(self)
+    }
+
+    fn inv(&self) -> Option<ExtFieldElement> {
+        ExtFieldElement::inv(self)
+    }
+
+    fn pow(&self, exponent: &Integer) -> ExtFieldElement {
+        ExtFieldElement::pow(self, exponent.clone()).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn sqrt(&self) -> Option<ExtFieldElement> {
+        ExtFieldElement::sqrt(self)
+    }
+
+    /// A uniformly random element, every coefficient is drawn uniformly from GF(p)
+    fn random<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> ExtFieldElement {
+        let base = self.field.base.zero();
+        let coeffs = (0..self.field.degree()).map(|_| base.random(rng)).collect();
+
+        self.field.with_polynomial(trim(coeffs))
+    }
+}
+
+/// Finds a monic irreducible polynomial of the given degree over GF(p), lowest degree first.
+///
+/// The candidates are tried in order, reading a counter in base min(p, 16) as the
+/// coefficients below the leading one. About one in `degree` polynomials is irreducible,
+/// so the search ends quickly.
+pub fn find_irreducible(base: &Arc<PrimeField>, degree: usize) -> Vec<FieldElement> {
+    assert!(degree > 0, "Degree must be positive");
+
+    let radix = base.prime().to_u32().map_or(16, |prime| prime.min(16));
+    let mut counter = Integer::ZERO;
+
+    loop {
+        let mut digits = counter.clone();
+        let mut candidate = Vec::with_capacity(degree + 1);
+        for _ in 0..degree {
+            candidate.push(base.element(Integer::from(digits.mod_u(radix))));
+            digits /= radix;
+        }
+        assert!(digits == 0, "No irreducible polynomial of degree {} found", degree);
+        candidate.push(base.one());
+
+        if is_irreducible(&candidate) {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// Rabin's test: a monic f of degree n is irreducible if and only if f divides x^(p^n) - x,
+/// and x^(p^(n/r)) - x is coprime to f for every prime r dividing n
+fn is_irreducible(f: &[FieldElement]) -> bool {
+    let degree = f.len() - 1;
+    let field = f[0].field();
+    let x = divide(&[field.zero(), field.one()], f).1;
+
+    // powers[i] = x^(p^(i+1)) mod f, each one is the p-th power of the previous one
+    let mut powers = Vec::with_capacity(degree);
+    let mut current = x.clone();
+    for _ in 0..degree {
+        current = pow_mod(&current, field.prime(), f);
+        powers.push(current.clone());
+    }
+
+    if powers[degree - 1] != x {
+        return false;
+    }
+
+    prime_divisors(degree).into_iter().all(|prime| {
+        let difference = subtract(&powers[degree / prime - 1], &x);
+        gcd(f, &difference).len() == 1
+    })
+}
+
+fn prime_divisors(mut n: usize) -> Vec<usize> {
+    let mut divisors = vec![];
+    let mut divisor = 2;
+    while divisor * divisor <= n {
+        if n.is_multiple_of(divisor) {
+            divisors.push(divisor);
+            while n.is_multiple_of(divisor) {
+                n /= divisor;
+            }
+        }
+        divisor += 1;
+    }
+    if n > 1 {
+        divisors.push(n);
+    }
+
+    divisors
+}
+
+fn trim(mut polynomial: Polynomial) -> Polynomial {
+    while polynomial.last().is_some_and(FieldElement::is_zero) {
+        polynomial.pop();
+    }
+
+    polynomial
+}
+
+fn add(a: &[FieldElement], b: &[FieldElement]) -> Polynomial {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    let mut sum = long.to_vec();
+    for (i, coeff) in short.iter().enumerate() {
+        sum[i] += coeff;
+    }
+
+    trim(sum)
+}
+
+fn subtract(a: &[FieldElement], b: &[FieldElement]) -> Polynomial {
+    let negated: Polynomial = b.iter().map(|coeff| -coeff).collect();
+    add(a, &negated)
+}
+
+fn multiply(a: &[FieldElement], b: &[FieldElement]) -> Polynomial {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut product = vec![a[0].field().zero(); a.len() + b.len() - 1];
+    for (i, left) in a.iter().enumerate() {
+        for (j, right) in b.iter().enumerate() {
+            product[i + j] += left * right;
+        }
+    }
+
+    trim(product)
+}
+
+/// Polynomial long division, returns the quotient and the remainder. `divisor` must be non-zero.
+fn divide(dividend: &[FieldElement], divisor: &[FieldElement]) -> (Polynomial, Polynomial) {
+    let field = divisor[0].field();
+    let lead_inverse = field.one() / divisor.last().unwrap();
+
+    let mut remainder = dividend.to_vec();
+    let mut quotient = vec![field.zero(); (dividend.len() + 1).saturating_sub(divisor.len())];
+
+    while remainder.len() >= divisor.len() {
+        let shift = remainder.len() - divisor.len();
+        let coeff = remainder.last().unwrap() * &lead_inverse;
+
+        for (i, term) in divisor.iter().enumerate() {
+            remainder[shift + i] -= &coeff * term;
+        }
+        quotient[shift] = coeff;
+        remainder = trim(remainder);
+    }
+
+    (trim(quotient), remainder)
+}
+
+/// The monic greatest common divisor
+fn gcd(a: &[FieldElement], b: &[FieldElement]) -> Polynomial {
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    while !b.is_empty() {
+        let remainder = divide(&a, &b).1;
+        a = b;
+        b = remainder;
+    }
+
+    match a.last() {
+        Some(lead) => {
+            let lead_inverse = lead.field().one() / lead;
+            a.iter().map(|coeff| coeff * &lead_inverse).collect()
+        }
+        None => a,
+    }
+}
+
+fn pow_mod(base: &[FieldElement], exponent: &Integer, modulus: &[FieldElement]) -> Polynomial {
+    let mut result = divide(&[modulus[0].field().one()], modulus).1;
+    for i in (0..exponent.significant_bits()).rev() {
+        result = divide(&multiply(&result, &result), modulus).1;
+        if exponent.get_bit(i) {
+            result = divide(&multiply(&result, base), modulus).1;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rug::Integer;
+
+    use crate::{rng, Field, FieldError, PrimeField};
+
+    use super::{find_irreducible, ExtField};
+
+    fn ints(nums: &[i64]) -> Vec<Integer> {
+        nums.iter().map(|&num| Integer::from(num)).collect()
+    }
+
+    #[test]
+    fn test_quadratic_extension() {
+        // x^2 + 1 is irreducible over GF(7) since -1 is not a square mod 7
+        let base = PrimeField::new(Integer::from(7)).unwrap();
+        let field = ExtField::new(&base, ints(&[1, 0, 1])).unwrap();
+        let (one, x) = (field.one(), field.x());
+
+        assert_eq!(field.order(), 49);
+        assert_eq!(&x * &x, field.element(ints(&[-1])));
+        assert_eq!((&one + &x) * (&one + &x), field.element(ints(&[0, 2])));
+        assert_eq!(3 * &x - 1, field.element(ints(&[-1, 3])));
+        assert_eq!(field.element(ints(&[3, 9, 0, 1])), field.element(ints(&[3, 1])));
+
+        for a in 0..7 {
+            for b in 0..7 {
+                let element = field.element(ints(&[a, b]));
+                // The Frobenius map is the conjugation a + bx -> a - bx
+                assert_eq!(element.frobenius(), field.element(ints(&[a, -b])));
+
+                if element.is_zero() {
+                    assert_eq!(element.inv(), None);
+                    continue;
+                }
+
+                assert_eq!(&element * element.inv().unwrap(), one);
+                assert_eq!(element.pow(Integer::from(48)).unwrap(), one);
+                assert_eq!(element.pow(Integer::from(-1)).unwrap(), element.inv().unwrap());
+            }
+        }
+
+        // Every element of GF(7) is a square in GF(49)
+        assert_eq!(field.element(ints(&[3])).sqrt().unwrap().pow(Integer::from(2)).unwrap(), field.element(ints(&[3])));
+        assert_eq!(format!("{:?}", field.element(ints(&[3, 5]))), "ExtFieldElement_7^2(3 + 5x)");
+    }
+
+    #[test]
+    fn test_reducible_modulus() {
+        let base = PrimeField::new(Integer::from(7)).unwrap();
+
+        // x^2 - 1 = (x - 1)(x + 1)
+        assert_eq!(ExtField::new(&base, ints(&[-1, 0, 1])), Err(FieldError::ReducibleModulus(ints(&[-1, 0, 1]))));
+        // (x^2 + 1)^2 has no roots but is still reducible
+        assert_eq!(ExtField::new(&base, ints(&[1, 0, 2, 0, 1])), Err(FieldError::ReducibleModulus(ints(&[1, 0, 2, 0, 1]))));
+        assert_eq!(ExtField::new(&base, ints(&[5])), Err(FieldError::ReducibleModulus(ints(&[5]))));
+
+        let other = ExtField::new(&base, ints(&[3, 1, 1])).unwrap();
+        let field = ExtField::new(&base, ints(&[1, 0, 1])).unwrap();
+        assert_eq!(field.one().checked_add(&other.one()), Err(FieldError::MismatchedExtensions));
+    }
+
+    #[test]
+    fn test_find_irreducible() {
+        for prime in [2, 3, 13, 223] {
+            let base = PrimeField::new(Integer::from(prime)).unwrap();
+
+            for degree in 1..=6 {
+                let modulus = find_irreducible(&base, degree);
+                let nums = modulus.iter().map(|coeff| coeff.num()).collect();
+
+                assert_eq!(modulus.len(), degree + 1);
+                assert!(ExtField::new(&base, nums).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_field_axioms() {
+        let mut rng = rng::deterministic(7);
+
+        for (prime, degree) in [(2, 8), (3, 5), (13, 4), (223, 3)] {
+            let base = PrimeField::new(Integer::from(prime)).unwrap();
+            let field = ExtField::with_degree(&base, degree);
+            let sample = field.zero();
+
+            for _ in 0..10 {
+                let a = sample.random(&mut rng);
+                let b = sample.random(&mut rng);
+
+                assert_eq!(Field::pow(&a, &field.order()), a);
+                assert_eq!((&a + &b).frobenius(), a.frobenius() + b.frobenius());
+                assert_eq!((&a * &b).frobenius(), a.frobenius() * b.frobenius());
+
+                // Applying the Frobenius map k times is the identity
+                let mut image = a.clone();
+                for _ in 0..degree {
+                    image = image.frobenius();
+                }
+                assert_eq!(image, a);
+
+                let square = &a * &a;
+                let root = Field::sqrt(&square).unwrap();
+                assert_eq!(root.clone() * root, square);
+
+                if !b.is_zero() {
+                    assert_eq!(&a / &b * b, a);
+                }
+            }
+
+            assert!(Arc::ptr_eq(sample.field(), &field));
+        }
+    }
+}
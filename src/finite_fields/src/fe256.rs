@@ -0,0 +1,311 @@
+use std::{fmt::Debug, sync::{Arc, OnceLock}};
+
+use rand::{CryptoRng, RngCore};
+use rug::{ops::RemRounding, Integer};
+
+use crate::{
+    ct::{self, Choice, ConstantTime},
+    limbs::{self, Limbs},
+    ops::{impl_binary_op, impl_neg, impl_scalar_op, impl_scalar_ops_reversed, impl_sum_product},
+    Field, FieldElement, FieldError, PrimeField,
+};
+
+/// p = 2^256 - 2^32 - 977, the prime of the secp256k1 coordinate field
+const MODULUS: Limbs = [
+    0xFFFF_FFFE_FFFF_FC2F,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+];
+
+/// 2^256 - p = 2^32 + 977
+const COMPLEMENT: [u64; 1] = [0x1_0000_03D1];
+
+/// p - 2, the exponent of the inverse by Fermat's little theorem
+const MODULUS_MINUS_TWO: Limbs = [MODULUS[0] - 2, MODULUS[1], MODULUS[2], MODULUS[3]];
+
+/// An element of the secp256k1 coordinate field, stored in four 64-bit limbs.
+///
+/// Unlike FieldElement it is `Copy` and never allocates. Reduction uses the special form
+/// of p: since 2^256 = 2^32 + 977 (mod p), the high half of a product is folded back
+/// with a single small multiplication.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Fe256(Limbs);
+
+impl Fe256 {
+    pub const ZERO: Fe256 = Fe256([0, 0, 0, 0]);
+    pub const ONE: Fe256 = Fe256([1, 0, 0, 0]);
+
+    /// The prime p as an Integer
+    pub fn modulus() -> Integer {
+        limbs::to_integer(&MODULUS)
+    }
+
+    /// The secp256k1 coordinate field as a shared PrimeField, for converting to FieldElement
+    pub fn field() -> Arc<PrimeField> {
+        static FIELD: OnceLock<Arc<PrimeField>> = OnceLock::new();
+
+        FIELD.get_or_init(|| PrimeField::new(Fe256::modulus()).unwrap()).clone()
+    }
+
+    pub fn from_u64(num: u64) -> Fe256 {
+        Fe256([num, 0, 0, 0])
+    }
+
+    /// Creates an element from any Integer, reducing it modulo p
+    pub fn from_integer(num: &Integer) -> Fe256 {
+        Fe256(limbs::from_integer(&num.clone().rem_euc(&Fe256::modulus())))
+    }
+
+    pub fn to_integer(&self) -> Integer {
+        limbs::to_integer(&self.0)
+    }
+
+    /// Parses a 32-byte big-endian number, returning None if it is not below p
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Fe256> {
+        let num = limbs::from_be_bytes(bytes);
+        if limbs::geq(&num, &MODULUS) {
+            return None;
+        }
+
+        Some(Fe256(num))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        limbs::to_be_bytes(&self.0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.0)
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub fn square(&self) -> Fe256 {
+        *self * *self
+    }
+
+    /// Raises the element to a non-negative power
+    pub fn pow(&self, exponent: &Integer) -> Fe256 {
+        Fe256(limbs::pow_mod(&self.0, exponent, &COMPLEMENT, &MODULUS))
+    }
+
+    /// The multiplicative inverse, or None for zero
+    pub fn invert(&self) -> Option<Fe256> {
+        if self.is_zero() {
+            return None;
+        }
+
+        Some(Fe256(limbs::invert_mod(&self.0, &MODULUS)))
+    }
+
+    /// A uniformly random element, by rejecting the 32-byte strings that are not below p
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Fe256 {
+        let mut bytes = [0u8; 32];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            if let Some(element) = Fe256::from_be_bytes(&bytes) {
+                return element;
+            }
+        }
+    }
+
+    /// A square root, or None if self is not a square.
+    /// Since p = 3 mod 4, the candidate root is self^((p+1)/4).
+    pub fn sqrt(&self) -> Option<Fe256> {
+        let root = self.pow(&((Fe256::modulus() + 1u32) >> 2));
+        if root.square() != *self {
+            return None;
+        }
+
+        Some(root)
+    }
+}
+
+/// Constant-time arithmetic, for secret values. See the `ct` module.
+impl Fe256 {
+    pub fn ct_add(&self, other: &Fe256) -> Fe256 {
+        Fe256(ct::add_mod(&self.0, &other.0, &MODULUS))
+    }
+
+    pub fn ct_sub(&self, other: &Fe256) -> Fe256 {
+        Fe256(ct::sub_mod(&self.0, &other.0, &MODULUS))
+    }
+
+    pub fn ct_neg(&self) -> Fe256 {
+        Fe256::ZERO.ct_sub(self)
+    }
+
+    pub fn ct_mul(&self, other: &Fe256) -> Fe256 {
+        Fe256(ct::mul_mod(&self.0, &other.0, &COMPLEMENT, &MODULUS))
+    }
+
+    pub fn ct_is_zero(&self) -> Choice {
+        self.ct_eq(&Fe256::ZERO)
+    }
+
+    /// The inverse as self^(p-2), with the same work for every value. Zero maps to zero.
+    pub fn ct_invert(&self) -> Fe256 {
+        Fe256(ct::pow_mod(&self.0, &MODULUS_MINUS_TWO, &COMPLEMENT, &MODULUS))
+    }
+}
+
+impl ConstantTime for Fe256 {
+    fn ct_eq(&self, other: &Fe256) -> Choice {
+        ct::eq(&self.0, &other.0)
+    }
+
+    fn ct_select(a: &Fe256, b: &Fe256, choice: Choice) -> Fe256 {
+        Fe256(ct::select(&a.0, &b.0, choice))
+    }
+}
+
+impl Field for Fe256 {
+    fn zero(&self) -> Fe256 {
+        Fe256::ZERO
+    }
+
+    fn one(&self) -> Fe256 {
+        Fe256::ONE
+    }
+
+    fn element(&self, num: &Integer) -> Fe256 {
+        Fe256::from_integer(num)
+    }
+
+    fn is_zero(&self) -> bool {
+        Fe256::is_zero(self)
+    }
+
+    fn inv(&self) -> Option<Fe256> {
+        self.invert()
+    }
+
+    fn pow(&self, exponent: &Integer) -> Fe256 {
+        Fe256::pow(self, exponent)
+    }
+
+    fn sqrt(&self) -> Option<Fe256> {
+        Fe256::sqrt(self)
+    }
+
+    fn random<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> Fe256 {
+        Fe256::random(rng)
+    }
+}
+
+impl Debug for Fe256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Fe256({})", self.to_integer().to_string_radix(16))
+    }
+}
+
+impl_binary_op!(Fe256, Add, add, AddAssign, add_assign, |a, b| Fe256(limbs::add_mod(&a.0, &b.0, &MODULUS)));
+impl_binary_op!(Fe256, Sub, sub, SubAssign, sub_assign, |a, b| Fe256(limbs::sub_mod(&a.0, &b.0, &MODULUS)));
+impl_binary_op!(Fe256, Mul, mul, MulAssign, mul_assign, |a, b| {
+    Fe256(limbs::mul_mod(&a.0, &b.0, &COMPLEMENT, &MODULUS))
+});
+
+impl_neg!(Fe256, |a| Fe256::ZERO - a);
+
+impl_scalar_op!(Fe256, u64, Add, add, AddAssign, add_assign, |a, n| a + Fe256::from_u64(n));
+impl_scalar_op!(Fe256, u64, Sub, sub, SubAssign, sub_assign, |a, n| a - Fe256::from_u64(n));
+impl_scalar_op!(Fe256, u64, Mul, mul, MulAssign, mul_assign, |a, n| a * Fe256::from_u64(n));
+impl_scalar_ops_reversed!(Fe256, u64);
+
+impl_sum_product!(Fe256, Fe256::ZERO, Fe256::ONE);
+
+impl TryFrom<&FieldElement> for Fe256 {
+    type Error = FieldError;
+
+    fn try_from(element: &FieldElement) -> Result<Fe256, FieldError> {
+        if element.order() != Fe256::modulus() {
+            return Err(FieldError::MismatchedModuli(element.order(), Fe256::modulus()));
+        }
+
+        Ok(Fe256(limbs::from_integer(&element.num())))
+    }
+}
+
+impl From<Fe256> for FieldElement {
+    fn from(element: Fe256) -> FieldElement {
+        Fe256::field().element(element.to_integer())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::{ops::Pow, Integer};
+
+    use crate::{FieldElement, FieldError};
+
+    use super::Fe256;
+
+    fn prime() -> Integer {
+        Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977)
+    }
+
+    #[test]
+    fn test_modulus() {
+        assert_eq!(Fe256::modulus(), prime());
+        assert_eq!(Fe256::from_integer(&prime()), Fe256::ZERO);
+        assert_eq!(Fe256::from_integer(&Integer::from(-1)), -Fe256::ONE);
+    }
+
+    #[test]
+    fn test_arithmetic_matches_integers() {
+        let p = prime();
+        let values = [
+            Integer::from(0),
+            Integer::from(1),
+            Integer::from(977),
+            p.clone() - 1,
+            p.clone() - Integer::from(2).pow(200),
+            Integer::from_str_radix("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798", 16).unwrap(),
+            Integer::from_str_radix("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8", 16).unwrap(),
+        ];
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let (fa, fb) = (Fe256::from_integer(a), Fe256::from_integer(b));
+
+                assert_eq!((fa + fb).to_integer(), (a.clone() + b) % &p);
+                assert_eq!((fa - fb).to_integer(), (a.clone() - b + &p) % &p);
+                assert_eq!((fa * fb).to_integer(), (a.clone() * b) % &p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invert() {
+        assert_eq!(Fe256::ZERO.invert(), None);
+
+        let a = Fe256::from_integer(&Integer::from_str_radix("deadbeef12345", 16).unwrap());
+        assert_eq!(a * a.invert().unwrap(), Fe256::ONE);
+        assert_eq!(a.pow(&Integer::from(3)), a * a * a);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = Fe256::from_integer(&(prime() - 2));
+        assert_eq!(Fe256::from_be_bytes(&a.to_be_bytes()), Some(a));
+        assert_eq!(Fe256::from_be_bytes(&[0xff; 32]), None);
+    }
+
+    #[test]
+    fn test_field_element_conversion() {
+        let element = Fe256::field().element(Integer::from(5000));
+        let fe = Fe256::try_from(&element).unwrap();
+
+        assert_eq!(fe, Fe256::from_u64(5000));
+        assert_eq!(FieldElement::from(fe), element);
+
+        let small = FieldElement::new(Integer::from(3), Integer::from(31));
+        assert_eq!(
+            Fe256::try_from(&small),
+            Err(FieldError::MismatchedModuli(Integer::from(31), prime()))
+        );
+    }
+}
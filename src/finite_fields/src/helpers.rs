@@ -1,64 +1,49 @@
-use std::{sync::{Arc, Mutex}, thread};
+use rand::RngCore;
+use rug::{integer::Order, Integer};
 
-use rug::Integer;
+use crate::{primality, FieldError};
 
 /// Check to ensure that a number is prime
+///
+/// See the `primality` module for the tests used.
 pub fn is_prime(num: Integer) -> bool {
-    // Numbers less than 1 are not prime
-    if num < 1 {
-        return false;
-    }
+    primality::is_prime(&num)
+}
 
-    if num.is_even() {
-        return false;
+/// A uniformly random Integer in [0, bound), `bound` must be positive.
+///
+/// Draws as many bits as `bound` has and retries when the result is too large,
+/// which happens less than half of the time.
+pub(crate) fn random_below<R: RngCore + ?Sized>(bound: &Integer, rng: &mut R) -> Integer {
+    let bits = bound.significant_bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+
+    loop {
+        rng.fill_bytes(&mut bytes);
+        bytes[0] &= 0xff >> (bytes.len() as u32 * 8 - bits);
+
+        let num = Integer::from_digits(&bytes, Order::Msf);
+        if num < *bound {
+            return num;
+        }
     }
+}
 
-    let num_threads = 10;
-    let square_root = num.clone().sqrt().to_u128().unwrap();
-    println!("The square root of {} is {}", num, square_root);
-
-    let mut is_prime = Arc::new(Mutex::new(true));
-
-    let chunk_size = square_root / num_threads;
-    let mut handles = vec![];
-
-    for i in 0..num_threads {
-        let start = Integer::from(3) + Integer::from(chunk_size * 1);
-        let end = if i == num_threads - 1 {
-            Integer::from(square_root)
-        } else {
-            start.clone() + Integer::from(chunk_size)
-        };
-
-        let num = num.clone();
-        let is_prime = Arc::clone(&is_prime);
-
-        // Spawn a thread for each range
-        let handle = thread::spawn(move || {
-            let mut current = start;
-
-            while current <= end {
-                if !*is_prime.lock().unwrap() {
-                    return;
-                }
-
-                if num.clone().div_rem(current.clone()).1 == 0 {
-                    *is_prime.clone().lock().unwrap() = false;
-                    return;
-                }
-
-                current += &Integer::from(2);
-            }
-        });
-
-        handles.push(handle);
-    }
+/// Encodes bytes as a lowercase hex string
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
 
-    for handle in handles {
-        handle.join().unwrap();
+/// Decodes a hex string of either case, an odd length or a non-hex digit is an error
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, FieldError> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(FieldError::InvalidHex(hex.to_string()));
     }
 
-    *is_prime.clone().lock().unwrap()
+    Ok((0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect())
 }
 
 #[cfg(test)]
@@ -69,11 +54,12 @@ mod tests {
     use crate::helpers::is_prime;
 
     #[test]
-    #[ignore]
     fn test_is_prime() {
         let prime = Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977);
         println!("The calculated prime for secp256 is {}", prime);
 
         assert!(is_prime(prime.clone()), "Not a prime number");
+        assert!(is_prime(Integer::from(2)), "Not a prime number");
+        assert!(!is_prime(prime + 2), "Not a composite number");
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,104 @@
-use std::{fmt::Debug, ops::{Add, Div, Mul, Rem, Sub}};
-use rug::{integer::IsPrime, ops::{Pow, RemRounding}};
+use std::{fmt::Debug, sync::Arc};
+use rand::{CryptoRng, RngCore};
+use rug::ops::RemRounding;
 
-use helpers::is_prime;
-use rug::{Integer, Assign};
+use rug::{integer::Order, Integer};
 
+mod batch;
+pub mod ct;
+pub mod error;
+pub mod extension;
+pub mod fe256;
 pub mod helpers;
+mod limbs;
+mod ops;
+pub mod prime_field;
+pub mod primality;
+mod rational;
+pub mod rng;
+pub mod scalar;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod traits;
+
+pub use batch::batch_invert;
+pub use ct::{Choice, ConstantTime};
+pub use error::FieldError;
+pub use extension::{ExtField, ExtFieldElement};
+pub use fe256::Fe256;
+pub use prime_field::PrimeField;
+pub use scalar::Scalar;
+#[cfg(feature = "serde")]
+pub use serde_impl::{ElementSeed, ElementsSeed, MAX_PRIME_BITS};
+pub use traits::Field;
+
+use ops::{impl_binary_op, impl_neg, impl_scalar_op, impl_scalar_ops_reversed};
 
 /// A FieldElement is a representation of an element in a finite field.
 #[derive(Clone)]
 pub struct FieldElement {
     num: Integer,
-    prime: Integer,
+    field: Arc<PrimeField>,
 }
 
 impl FieldElement {
     /// Creates a new field element 
+    ///
+    /// Panics if `prime` is not a prime number, see `try_new` for the fallible version.
+    /// Every call re-tests the primality of `prime`, use `PrimeField::element` to create
+    /// many elements of the same field.
     pub fn new(num: Integer, prime: Integer) -> FieldElement {
-        // assert!(is_prime(prime.clone()), "Number must be prime");
-        assert_ne!(prime.is_probably_prime(30), IsPrime::No, "Number must be prime");
+        FieldElement::try_new(num, prime).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new field element, returning an error if `prime` is not a prime number
+    pub fn try_new(num: Integer, prime: Integer) -> Result<FieldElement, FieldError> {
+        Ok(PrimeField::new(prime)?.element(num))
+    }
+
+    /// Creates an element from an already reduced `num`, skipping all checks
+    pub(crate) fn from_field(num: Integer, field: Arc<PrimeField>) -> FieldElement {
+        FieldElement { num, field }
+    }
+
+    /// A uniformly random element of `field`.
+    /// Pass `rng::OsRng` for secrets, or `rng::deterministic` in tests.
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(field: &Arc<PrimeField>, rng: &mut R) -> FieldElement {
+        FieldElement::from_field(helpers::random_below(field.prime(), rng), Arc::clone(field))
+    }
+
+    /// The big-endian encoding of the number, always `PrimeField::byte_length` bytes long
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let digits = self.num.to_digits::<u8>(Order::Msf);
+
+        let mut bytes = vec![0u8; self.field.byte_length() - digits.len()];
+        bytes.extend(digits);
+        bytes
+    }
+
+    /// Parses the encoding from `to_bytes`. The length must match the field and the
+    /// number must be below the prime, it is never reduced.
+    pub fn from_bytes(field: &Arc<PrimeField>, bytes: &[u8]) -> Result<FieldElement, FieldError> {
+        if bytes.len() != field.byte_length() {
+            return Err(FieldError::InvalidLength(field.byte_length(), bytes.len()));
+        }
+
+        let num = Integer::from_digits(bytes, Order::Msf);
+        if num >= *field.prime() {
+            return Err(FieldError::UnreducedValue(num, field.prime().clone()));
+        }
+
+        Ok(FieldElement::from_field(num, Arc::clone(field)))
+    }
+
+    /// `to_bytes` as a lowercase hex string
+    pub fn to_hex(&self) -> String {
+        helpers::to_hex(&self.to_bytes())
+    }
 
-        FieldElement { num: num % prime.clone(), prime }
+    /// Parses the hex string from `to_hex`, with the same checks as `from_bytes`
+    pub fn from_hex(field: &Arc<PrimeField>, hex: &str) -> Result<FieldElement, FieldError> {
+        FieldElement::from_bytes(field, &helpers::from_hex(hex)?)
     }
 
     pub fn num(&self) -> Integer {
@@ -27,37 +106,185 @@ impl FieldElement {
     }
 
     pub fn order(&self) -> Integer {
-        self.prime.clone()
+        self.field.prime().clone()
+    }
+
+    /// The field this element belongs to
+    pub fn field(&self) -> &Arc<PrimeField> {
+        &self.field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
     }
 
-    pub fn pow(&self, exponent: Integer) -> Result<FieldElement, ()> {
+    /// Raises self to any power, negative ones included.
+    ///
+    /// Not constant time, the timing depends on both operands. Use the `ct_*` methods of
+    /// Fe256 and Scalar for secrets.
+    pub fn pow(&self, exponent: Integer) -> Result<FieldElement, FieldError> {
+        // A negative power of zero would require inverting zero
+        if exponent < 0 && self.is_zero() {
+            return Err(FieldError::DivisionByZero);
+        }
+
+        // By Fermat's little theorem a^(p-1) = 1, so negative exponents
+        // can be brought back into the range [0, p - 1)
         let expo = if exponent > 0 {
             exponent
         } else { 
-            exponent + (self.prime.clone() - Integer::from(1))
+            exponent.rem_euc(self.order() - Integer::from(1))
         };
         
         match self.num.clone()
-            .pow_mod(&expo, &self.prime) 
+            .pow_mod(&expo, self.field.prime()) 
             {
-                Ok(num) => Ok(FieldElement::new(num.rem_euc(self.prime.clone()), self.prime.clone())),
-                Err(_) => Err(())
+                Ok(num) => Ok(self.with_num(num)),
+                Err(_) => Err(FieldError::DivisionByZero)
+            }
+    }
+
+    /// The Legendre symbol (a/p): 1 if self is a non-zero square, -1 if it is not a square
+    /// and 0 if self is zero
+    pub fn legendre(&self) -> i32 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        // Every element of GF(2) is a square
+        if *self.field.prime() == 2 {
+            return 1;
+        }
+
+        self.num.legendre(self.field.prime())
+    }
+
+    /// Whether self is a quadratic residue, zero counts as a square
+    pub fn is_square(&self) -> bool {
+        self.legendre() != -1
+    }
+
+    /// A square root of self, or None if self is not a square.
+    /// The other root is the negation of the returned one.
+    pub fn sqrt(&self) -> Option<FieldElement> {
+        match self.legendre() {
+            -1 => return None,
+            0 => return Some(self.clone()),
+            _ => {}
+        }
+
+        let prime = self.order();
+        if prime == 2 {
+            return Some(self.clone());
+        }
+
+        // When p = 3 mod 4, a^((p+1)/4) squares to a^((p+1)/2) = a * a^((p-1)/2) = a
+        if prime.mod_u(4) == 3 {
+            return self.pow((prime + 1u32) / 4u32).ok();
+        }
+
+        Some(self.tonelli_shanks())
+    }
+
+    /// Tonelli-Shanks square root for any odd prime, self must be a non-zero square
+    fn tonelli_shanks(&self) -> FieldElement {
+        let one = self.field.one();
+
+        // Write p - 1 = q * 2^s with q odd
+        let p_minus_one = self.order() - 1u32;
+        let s = p_minus_one.find_one(0).unwrap();
+        let q = p_minus_one.clone() >> s;
+
+        // Any quadratic non-residue z will do, half of the field are
+        let mut z = self.field.element(Integer::from(2));
+        while z.legendre() != -1 {
+            z += 1;
+        }
+
+        let mut m = s;
+        let mut c = z.pow(q.clone()).unwrap();
+        let mut t = self.pow(q.clone()).unwrap();
+        let mut root = self.pow((q + 1u32) / 2u32).unwrap();
+
+        // Invariant: root^2 = self * t, where t has order 2^i for some i < m
+        while t != one {
+            let mut i = 0;
+            let mut t_power = t.clone();
+            while t_power != one {
+                t_power *= t_power.clone();
+                i += 1;
             }
+
+            let b = c.pow(Integer::from(1) << (m - i - 1)).unwrap();
+            m = i;
+            c = &b * &b;
+            t *= &c;
+            root *= b;
+        }
+
+        root
+    }
+
+    pub fn checked_add(&self, other: &FieldElement) -> Result<FieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        let num = (self.num.clone() + &other.num).rem_euc(self.field.prime());
+        Ok(self.with_num(num))
+    }
+
+    pub fn checked_sub(&self, other: &FieldElement) -> Result<FieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        let num = (self.num.clone() - &other.num).rem_euc(self.field.prime());
+        Ok(self.with_num(num))
+    }
+
+    pub fn checked_mul(&self, other: &FieldElement) -> Result<FieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        let num = (self.num.clone() * &other.num).rem_euc(self.field.prime());
+        Ok(self.with_num(num))
+    }
+
+    /// Divides by `other`, i.e multiplies by its inverse `other^(p-2)`.
+    /// Not constant time, like `pow`.
+    pub fn checked_div(&self, other: &FieldElement) -> Result<FieldElement, FieldError> {
+        self.ensure_same_field(other)?;
+
+        if other.is_zero() {
+            return Err(FieldError::DivisionByZero);
+        }
+
+        let divisor = other.pow(other.order() - Integer::from(2))?;
+        self.checked_mul(&divisor)
     }
 
-    fn is_equal(&self, other: &FieldElement) -> bool {
+    pub fn is_equal(&self, other: &FieldElement) -> bool {
         self == other
     }
 
-    fn is_not_equal(&self, other: &FieldElement) -> bool {
+    pub fn is_not_equal(&self, other: &FieldElement) -> bool {
         !self.is_equal(other)
     }
+
+    /// Builds an element of the same field from an already reduced `num`
+    fn with_num(&self, num: Integer) -> FieldElement {
+        FieldElement { num, field: Arc::clone(&self.field) }
+    }
+
+    fn ensure_same_field(&self, other: &FieldElement) -> Result<(), FieldError> {
+        if !PrimeField::is_same(&self.field, &other.field) {
+            return Err(FieldError::MismatchedModuli(self.order(), other.order()));
+        }
+
+        Ok(())
+    }
 }
 
 // Includes a formatter for the FieldElement struct
 impl Debug for FieldElement {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "FieldElement_{}({})", self.prime, self.num)
+        write!(f, "FieldElement_{}({})", self.field.prime(), self.num)
     }
 }
 
@@ -65,56 +292,84 @@ impl Debug for FieldElement {
 /// Allows using operator `==` and `!=` to compare FieldElement instances
 impl PartialEq for FieldElement {
     fn eq(&self, other: &Self) -> bool {
-        self.num == other.num && self.prime == other.prime
+        self.num == other.num && PrimeField::is_same(&self.field, &other.field)
     }
 }
 
 impl Eq for FieldElement {}
 
-impl Add for FieldElement {
-    type Output = FieldElement;
-
-    fn add(self, other: FieldElement) -> FieldElement {
-        assert_eq!(self.prime, other.prime, "Primes must be equal");
-
-        let num = (self.num + other.num).rem_euc(self.prime.clone());
-        FieldElement::new(num, self.prime)
+// The operators panic on invalid operands, the checked_* methods are the fallible equivalents
+
+impl_binary_op!(FieldElement, Add, add, AddAssign, add_assign, |a, b| {
+    a.checked_add(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(FieldElement, Sub, sub, SubAssign, sub_assign, |a, b| {
+    a.checked_sub(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(FieldElement, Mul, mul, MulAssign, mul_assign, |a, b| {
+    a.checked_mul(b).unwrap_or_else(|err| panic!("{}", err))
+});
+impl_binary_op!(FieldElement, Div, div, DivAssign, div_assign, |a, b| {
+    a.checked_div(b).unwrap_or_else(|err| panic!("{}", err))
+});
+
+impl_neg!(FieldElement, |a| a.with_num((-a.num.clone()).rem_euc(a.field.prime())));
+
+// Mixed operations with integers, e.g. `3 * x` or `x + Integer::from(5)`
+
+impl_scalar_op!(FieldElement, u64, Add, add, AddAssign, add_assign, |a, n| {
+    a.with_num((a.num.clone() + n).rem_euc(a.field.prime()))
+});
+impl_scalar_op!(FieldElement, u64, Sub, sub, SubAssign, sub_assign, |a, n| {
+    a.with_num((a.num.clone() - n).rem_euc(a.field.prime()))
+});
+impl_scalar_op!(FieldElement, u64, Mul, mul, MulAssign, mul_assign, |a, n| {
+    a.with_num((a.num.clone() * n).rem_euc(a.field.prime()))
+});
+impl_scalar_ops_reversed!(FieldElement, u64);
+
+impl_scalar_op!(FieldElement, Integer, Add, add, AddAssign, add_assign, |a, n| {
+    a.with_num((n + &a.num).rem_euc(a.field.prime()))
+});
+impl_scalar_op!(FieldElement, Integer, Sub, sub, SubAssign, sub_assign, |a, n| {
+    a.with_num((a.num.clone() - n).rem_euc(a.field.prime()))
+});
+impl_scalar_op!(FieldElement, Integer, Mul, mul, MulAssign, mul_assign, |a, n| {
+    a.with_num((n * &a.num).rem_euc(a.field.prime()))
+});
+impl_scalar_ops_reversed!(FieldElement, Integer);
+
+impl Field for FieldElement {
+    fn zero(&self) -> FieldElement {
+        self.field.zero()
     }
-}
-
-impl Sub for FieldElement {
-    type Output = FieldElement;
 
-    fn sub(self, other: FieldElement) -> FieldElement {
-        assert_eq!(self.prime, other.prime, "Primes must be equal");
-
-        let num = (self.num - other.num).rem_euc(self.prime.clone());
-        println!("Num: {}", (15 % 31));
-        FieldElement::new(num, self.prime)
+    fn one(&self) -> FieldElement {
+        self.field.one()
     }
-}
 
-impl Mul for FieldElement {
-    type Output = FieldElement;
-
-    fn mul(self, other: FieldElement) -> FieldElement {
-        assert_eq!(self.prime, other.prime, "Primes must be equal");
+    fn element(&self, num: &Integer) -> FieldElement {
+        self.field.element(num.clone())
+    }
 
-        let num = (self.num * other.num).rem_euc(self.prime.clone());
-        FieldElement::new(num, self.prime)
+    fn is_zero(&self) -> bool {
+        FieldElement::is_zero(self)
     }
-}
 
-impl Div for FieldElement {
-    type Output = FieldElement;
+    fn inv(&self) -> Option<FieldElement> {
+        self.one().checked_div(self).ok()
+    }
 
-    fn div(self, other: FieldElement) -> FieldElement {
-        assert_eq!(self.prime, other.prime, "Primes must be equal");
+    fn pow(&self, exponent: &Integer) -> FieldElement {
+        FieldElement::pow(self, exponent.clone()).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        let divisor = other.pow(other.prime.clone() - Integer::from(2)).unwrap();
+    fn sqrt(&self) -> Option<FieldElement> {
+        FieldElement::sqrt(self)
+    }
 
-        let num = (self.num * divisor.num).rem_euc(self.prime.clone());
-        FieldElement::new(num, self.prime)
+    fn random<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> FieldElement {
+        FieldElement::random(&self.field, rng)
     }
 }
 
@@ -122,6 +377,8 @@ impl Div for FieldElement {
 mod tests {
     use std::panic;
 
+    use rug::Assign;
+
     use super::*;
 
     #[test]
@@ -233,4 +490,202 @@ mod tests {
         let b = FieldElement::new(Integer::from(11), Integer::from(31));
         assert_eq!(a.pow(Integer::from(-4)).unwrap() * b, FieldElement::new(Integer::from(13), Integer::from(31)));
     }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            FieldElement::try_new(Integer::from(3), Integer::from(20)),
+            Err(FieldError::NonPrimeModulus(Integer::from(20)))
+        );
+        assert_eq!(
+            FieldElement::try_new(Integer::from(3), Integer::from(1)),
+            Err(FieldError::NonPrimeModulus(Integer::from(1)))
+        );
+
+        let a = FieldElement::try_new(Integer::from(-3), Integer::from(31)).unwrap();
+        assert_eq!(a.num(), Integer::from(28));
+    }
+
+    #[test]
+    fn test_checked_operations() {
+        let a = FieldElement::new(Integer::from(17), Integer::from(31));
+        let b = FieldElement::new(Integer::from(21), Integer::from(31));
+        let c = FieldElement::new(Integer::from(21), Integer::from(29));
+
+        assert_eq!(a.checked_add(&b), Ok(FieldElement::new(Integer::from(7), Integer::from(31))));
+        assert_eq!(a.checked_sub(&b), Ok(FieldElement::new(Integer::from(27), Integer::from(31))));
+        assert_eq!(a.checked_mul(&b), Ok(FieldElement::new(Integer::from(16), Integer::from(31))));
+        assert_eq!(a.checked_div(&b), Ok(FieldElement::new(Integer::from(20), Integer::from(31))));
+
+        let mismatched = Err(FieldError::MismatchedModuli(Integer::from(31), Integer::from(29)));
+        assert_eq!(a.checked_add(&c), mismatched);
+        assert_eq!(a.checked_sub(&c), mismatched);
+        assert_eq!(a.checked_mul(&c), mismatched);
+        assert_eq!(a.checked_div(&c), mismatched);
+    }
+
+    #[test]
+    fn test_reference_operators() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let a = field.element(Integer::from(17));
+        let b = field.element(Integer::from(21));
+
+        assert_eq!(&a + &b, a.clone() + b.clone());
+        assert_eq!(&a - &b, field.element(Integer::from(27)));
+        assert_eq!(&a * b.clone(), field.element(Integer::from(16)));
+        assert_eq!(a.clone() / &b, field.element(Integer::from(20)));
+        assert_eq!(-&a, field.element(Integer::from(14)));
+        assert_eq!(-field.zero(), field.zero());
+
+        let mut c = a.clone();
+        c += &b;
+        c *= b.clone();
+        c -= &a;
+        c /= &b;
+        assert_eq!(c, (&a + &b) * &b / &b - &a / &b);
+    }
+
+    #[test]
+    fn test_scalar_operators() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let x = field.element(Integer::from(17));
+
+        assert_eq!(3 * &x, &x + &x + &x);
+        assert_eq!(&x * 3, 3 * x.clone());
+        assert_eq!(x.clone() + 20, field.element(Integer::from(6)));
+        assert_eq!(&x - 20, field.element(Integer::from(28)));
+        assert_eq!(1 - &x, field.element(Integer::from(15)));
+        assert_eq!(Integer::from(-2) * &x, -(&x + &x));
+        assert_eq!(&x + Integer::from(14), field.zero());
+
+        let mut y = x.clone();
+        y *= 2;
+        y += 1;
+        assert_eq!(y, 2 * x + 1);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let elements: Vec<FieldElement> = (1..=5).map(|num| field.element(Integer::from(num))).collect();
+
+        assert_eq!(field.sum(&elements), field.element(Integer::from(15)));
+        assert_eq!(field.product(&elements), field.element(Integer::from(120)));
+        assert_eq!(field.sum(elements), field.element(Integer::from(15)));
+
+        // The empty sum and product are the zero and one of the field
+        assert_eq!(field.sum(Vec::<FieldElement>::new()), field.zero());
+        assert_eq!(field.product(Vec::<FieldElement>::new()), field.one());
+    }
+
+    #[test]
+    fn test_random() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let mut rng = rng::deterministic(1);
+
+        // Every element shows up among enough uniform samples
+        let mut seen = [false; 31];
+        for _ in 0..1000 {
+            let element = FieldElement::random(&field, &mut rng);
+            seen[element.num().to_usize().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+
+        assert_eq!(
+            FieldElement::random(&field, &mut rng::deterministic(5)),
+            FieldElement::random(&field, &mut rng::deterministic(5))
+        );
+
+        let secp = Fe256::field();
+        let secret = FieldElement::random(&secp, &mut rng::OsRng);
+        assert!(PrimeField::is_same(secret.field(), &secp));
+    }
+
+    #[test]
+    fn test_bytes() {
+        let field = PrimeField::new(Integer::from(65537)).unwrap();
+        let element = field.element(Integer::from(258));
+
+        assert_eq!(element.to_bytes(), vec![0, 1, 2]);
+        assert_eq!(element.to_hex(), "000102");
+        assert_eq!(field.zero().to_hex(), "000000");
+        assert_eq!(FieldElement::from_bytes(&field, &[0, 1, 2]), Ok(element.clone()));
+        assert_eq!(FieldElement::from_hex(&field, "000102"), Ok(element));
+
+        assert_eq!(FieldElement::from_bytes(&field, &[1, 2]), Err(FieldError::InvalidLength(3, 2)));
+        assert_eq!(
+            FieldElement::from_hex(&field, "010001"),
+            Err(FieldError::UnreducedValue(Integer::from(65537), Integer::from(65537)))
+        );
+        assert_eq!(FieldElement::from_hex(&field, "00010"), Err(FieldError::InvalidHex("00010".to_string())));
+        assert_eq!(FieldElement::from_hex(&field, "0001zz"), Err(FieldError::InvalidHex("0001zz".to_string())));
+
+        let secp = Fe256::field();
+        let generator_x = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let x = FieldElement::from_hex(&secp, generator_x).unwrap();
+        assert_eq!(x.to_hex(), generator_x);
+        assert_eq!(x.to_bytes(), Fe256::try_from(&x).unwrap().to_be_bytes().to_vec());
+        assert_eq!(secp.element(Integer::from(1)).to_bytes().len(), 32);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // 31 and 223 take the (p+1)/4 path, the others go through Tonelli-Shanks
+        for prime in [31, 223, 13, 17, 41, 97] {
+            let field = PrimeField::new(Integer::from(prime)).unwrap();
+            let mut squares = 0;
+
+            for num in 1..prime {
+                let a = field.element(Integer::from(num));
+
+                match a.sqrt() {
+                    Some(root) => {
+                        assert!(a.is_square());
+                        assert_eq!(a.legendre(), 1);
+                        assert_eq!(root.clone() * root, a, "Wrong root of {:?}", a);
+                        squares += 1;
+                    }
+                    None => {
+                        assert!(!a.is_square());
+                        assert_eq!(a.legendre(), -1);
+                    }
+                }
+            }
+
+            // Exactly half of the non-zero elements are squares
+            assert_eq!(squares, (prime - 1) / 2);
+            assert_eq!(field.zero().sqrt(), Some(field.zero()));
+            assert_eq!(field.zero().legendre(), 0);
+        }
+    }
+
+    #[test]
+    fn test_sqrt_known_values() {
+        let a = FieldElement::new(Integer::from(5), Integer::from(31));
+        let root = a.sqrt().unwrap();
+        assert!(root.num() == 6 || root.num() == 25);
+
+        let a = FieldElement::new(Integer::from(3), Integer::from(31));
+        assert_eq!(a.sqrt(), None);
+
+        // y^2 = 47^3 + 7 on the p=223 curve, where (47, 71) is a point
+        let x = FieldElement::new(Integer::from(47), Integer::from(223));
+        let y_squared = x.pow(Integer::from(3)).unwrap() + FieldElement::new(Integer::from(7), Integer::from(223));
+        let y = y_squared.sqrt().unwrap();
+        assert!(y.num() == 71 || y.num() == 223 - 71);
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let a = FieldElement::new(Integer::from(3), Integer::from(31));
+        let zero = FieldElement::new(Integer::from(0), Integer::from(31));
+
+        assert_eq!(a.checked_div(&zero), Err(FieldError::DivisionByZero));
+        assert_eq!(zero.pow(Integer::from(-1)), Err(FieldError::DivisionByZero));
+        assert_eq!(zero.pow(Integer::from(0)), Ok(FieldElement::new(Integer::from(1), Integer::from(31))));
+        assert_eq!(zero.pow(Integer::from(5)), Ok(zero.clone()));
+
+        let result = panic::catch_unwind(|| a / zero);
+        assert!(result.is_err(), "Division by zero must panic");
+    }
 }
@@ -0,0 +1,218 @@
+// Fixed-width 256-bit arithmetic on little-endian 64-bit limbs, shared by Fe256 and Scalar.
+
+use rug::{integer::Order, Integer};
+
+pub(crate) type Limbs = [u64; 4];
+
+/// a + b + carry, returning the result and the new carry
+#[inline]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// a - b - borrow, returning the result and the new borrow (0 or 1)
+#[inline]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// a + b * c + carry, returning the low and high words
+#[inline]
+pub(crate) fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128 * c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+pub(crate) fn add(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = [0u64; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        (result[i], carry) = adc(a[i], b[i], carry);
+    }
+    (result, carry)
+}
+
+pub(crate) fn sub(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (result[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    (result, borrow)
+}
+
+/// Returns true if a >= b
+pub(crate) fn geq(a: &Limbs, b: &Limbs) -> bool {
+    sub(a, b).1 == 0
+}
+
+pub(crate) fn is_zero(a: &Limbs) -> bool {
+    a.iter().all(|&limb| limb == 0)
+}
+
+/// (a + b) mod m, for a and b already reduced
+pub(crate) fn add_mod(a: &Limbs, b: &Limbs, modulus: &Limbs) -> Limbs {
+    let (sum, carry) = add(a, b);
+    // The sum is below 2m, so a single subtraction brings it back in range.
+    // When the addition overflowed, the wrapping subtraction still gives the right value.
+    if carry != 0 || geq(&sum, modulus) {
+        sub(&sum, modulus).0
+    } else {
+        sum
+    }
+}
+
+/// (a - b) mod m, for a and b already reduced
+pub(crate) fn sub_mod(a: &Limbs, b: &Limbs, modulus: &Limbs) -> Limbs {
+    let (difference, borrow) = sub(a, b);
+    if borrow != 0 {
+        add(&difference, modulus).0
+    } else {
+        difference
+    }
+}
+
+/// Schoolbook multiplication into a 512-bit result
+pub(crate) fn mul_wide(a: &Limbs, b: &Limbs) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (result[i + j], carry) = mac(result[i + j], a[i], b[j], carry);
+        }
+        result[i + 4] = carry;
+    }
+    result
+}
+
+/// Reduces a 512-bit value modulo m, where `complement` = 2^256 - m.
+///
+/// Moduli just below 2^256 (like the secp256k1 p and n) have a small complement, and
+/// since 2^256 = complement (mod m), the high half can be folded onto the low half with
+/// `hi * 2^256 + lo = hi * complement + lo` until it vanishes.
+pub(crate) fn reduce_wide(mut wide: [u64; 8], complement: &[u64], modulus: &Limbs) -> Limbs {
+    while wide[4..].iter().any(|&limb| limb != 0) {
+        let mut next = [0u64; 8];
+        next[..4].copy_from_slice(&wide[..4]);
+
+        for (i, &high) in wide[4..].iter().enumerate() {
+            let mut carry = 0;
+            for (j, &c) in complement.iter().enumerate() {
+                (next[i + j], carry) = mac(next[i + j], high, c, carry);
+            }
+
+            let mut k = i + complement.len();
+            while carry != 0 {
+                (next[k], carry) = adc(next[k], carry, 0);
+                k += 1;
+            }
+        }
+
+        wide = next;
+    }
+
+    let mut result = [wide[0], wide[1], wide[2], wide[3]];
+    while geq(&result, modulus) {
+        result = sub(&result, modulus).0;
+    }
+    result
+}
+
+pub(crate) fn mul_mod(a: &Limbs, b: &Limbs, complement: &[u64], modulus: &Limbs) -> Limbs {
+    reduce_wide(mul_wide(a, b), complement, modulus)
+}
+
+/// Square-and-multiply exponentiation, `exponent` must be non-negative
+pub(crate) fn pow_mod(base: &Limbs, exponent: &Integer, complement: &[u64], modulus: &Limbs) -> Limbs {
+    let mut result = [1, 0, 0, 0];
+    for i in (0..exponent.significant_bits()).rev() {
+        result = mul_mod(&result, &result, complement, modulus);
+        if exponent.get_bit(i) {
+            result = mul_mod(&result, base, complement, modulus);
+        }
+    }
+    result
+}
+
+/// Halves a value whose bit 256 is `carry`
+fn shr1(a: &Limbs, carry: u64) -> Limbs {
+    [
+        (a[0] >> 1) | (a[1] << 63),
+        (a[1] >> 1) | (a[2] << 63),
+        (a[2] >> 1) | (a[3] << 63),
+        (a[3] >> 1) | (carry << 63),
+    ]
+}
+
+/// x / 2 mod m, for an odd modulus m
+fn half_mod(x: &Limbs, modulus: &Limbs) -> Limbs {
+    if x[0] & 1 == 0 {
+        shr1(x, 0)
+    } else {
+        let (sum, carry) = add(x, modulus);
+        shr1(&sum, carry)
+    }
+}
+
+/// The inverse of a non-zero `a` modulo an odd prime, with the binary extended Euclidean algorithm.
+///
+/// It only uses shifts and subtractions, which is much cheaper than exponentiating to m - 2,
+/// but its running time depends on the value of `a`.
+pub(crate) fn invert_mod(a: &Limbs, modulus: &Limbs) -> Limbs {
+    const ONE: Limbs = [1, 0, 0, 0];
+
+    // Invariants: x1 * a = u and x2 * a = v (mod m)
+    let (mut u, mut v) = (*a, *modulus);
+    let (mut x1, mut x2) = (ONE, [0u64; 4]);
+
+    while u != ONE && v != ONE {
+        while u[0] & 1 == 0 {
+            u = shr1(&u, 0);
+            x1 = half_mod(&x1, modulus);
+        }
+        while v[0] & 1 == 0 {
+            v = shr1(&v, 0);
+            x2 = half_mod(&x2, modulus);
+        }
+
+        if geq(&u, &v) {
+            u = sub(&u, &v).0;
+            x1 = sub_mod(&x1, &x2, modulus);
+        } else {
+            v = sub(&v, &u).0;
+            x2 = sub_mod(&x2, &x1, modulus);
+        }
+    }
+
+    if u == ONE { x1 } else { x2 }
+}
+
+pub(crate) fn from_be_bytes(bytes: &[u8; 32]) -> Limbs {
+    let mut limbs = [0u64; 4];
+    for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+        limbs[3 - i] = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+pub(crate) fn to_be_bytes(limbs: &Limbs) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+        chunk.copy_from_slice(&limbs[3 - i].to_be_bytes());
+    }
+    bytes
+}
+
+/// Converts a non-negative Integer below 2^256
+pub(crate) fn from_integer(num: &Integer) -> Limbs {
+    let mut limbs = [0u64; 4];
+    let digits = num.to_digits::<u64>(Order::Lsf);
+    limbs[..digits.len()].copy_from_slice(&digits);
+    limbs
+}
+
+pub(crate) fn to_integer(limbs: &Limbs) -> Integer {
+    Integer::from_digits(limbs, Order::Lsf)
+}
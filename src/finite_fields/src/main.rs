@@ -1,9 +1,5 @@
-use finite_fields::FieldElement;
-use finite_fields::helpers::is_prime;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
-use rug::Integer;
-use rug::ops::Pow;
 
 fn main() {
     // let field_a = FieldElement::new(
@@ -0,0 +1,192 @@
+// Macros for the operator impls of the field types. Each operator is written once with
+// both operands borrowed, the macros forward every owned/borrowed combination to it.
+
+/// `a op b` for every combination of owned and borrowed operands, and `a op= b`
+macro_rules! impl_binary_op {
+    ($type:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl std::ops::$trait<&$type> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: &$type) -> $type {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl std::ops::$trait<$type> for $type {
+            type Output = $type;
+
+            fn $method(self, other: $type) -> $type {
+                std::ops::$trait::$method(&self, &other)
+            }
+        }
+
+        impl std::ops::$trait<&$type> for $type {
+            type Output = $type;
+
+            fn $method(self, other: &$type) -> $type {
+                std::ops::$trait::$method(&self, other)
+            }
+        }
+
+        impl std::ops::$trait<$type> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: $type) -> $type {
+                std::ops::$trait::$method(self, &other)
+            }
+        }
+
+        impl std::ops::$assign_trait<&$type> for $type {
+            fn $assign_method(&mut self, other: &$type) {
+                *self = std::ops::$trait::$method(&*self, other);
+            }
+        }
+
+        impl std::ops::$assign_trait<$type> for $type {
+            fn $assign_method(&mut self, other: $type) {
+                *self = std::ops::$trait::$method(&*self, &other);
+            }
+        }
+    };
+}
+
+/// `a op n` and `a op= n` for a scalar n, such as a u64 or an Integer
+macro_rules! impl_scalar_op {
+    ($type:ty, $scalar:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, |$a:ident, $n:ident| $body:expr) => {
+        impl std::ops::$trait<$scalar> for &$type {
+            type Output = $type;
+
+            fn $method(self, other: $scalar) -> $type {
+                let ($a, $n) = (self, other);
+                $body
+            }
+        }
+
+        impl std::ops::$trait<$scalar> for $type {
+            type Output = $type;
+
+            fn $method(self, other: $scalar) -> $type {
+                std::ops::$trait::$method(&self, other)
+            }
+        }
+
+        impl std::ops::$assign_trait<$scalar> for $type {
+            fn $assign_method(&mut self, other: $scalar) {
+                *self = std::ops::$trait::$method(&*self, other);
+            }
+        }
+    };
+}
+
+/// `n + a`, `n - a` and `n * a` for a scalar n on the left
+macro_rules! impl_scalar_ops_reversed {
+    ($type:ty, $scalar:ty) => {
+        impl std::ops::Add<&$type> for $scalar {
+            type Output = $type;
+
+            fn add(self, other: &$type) -> $type {
+                other + self
+            }
+        }
+
+        impl std::ops::Add<$type> for $scalar {
+            type Output = $type;
+
+            fn add(self, other: $type) -> $type {
+                &other + self
+            }
+        }
+
+        impl std::ops::Sub<&$type> for $scalar {
+            type Output = $type;
+
+            fn sub(self, other: &$type) -> $type {
+                -(other - self)
+            }
+        }
+
+        impl std::ops::Sub<$type> for $scalar {
+            type Output = $type;
+
+            fn sub(self, other: $type) -> $type {
+                -(&other - self)
+            }
+        }
+
+        impl std::ops::Mul<&$type> for $scalar {
+            type Output = $type;
+
+            fn mul(self, other: &$type) -> $type {
+                other * self
+            }
+        }
+
+        impl std::ops::Mul<$type> for $scalar {
+            type Output = $type;
+
+            fn mul(self, other: $type) -> $type {
+                &other * self
+            }
+        }
+    };
+}
+
+/// `-a` for owned and borrowed operands
+macro_rules! impl_neg {
+    ($type:ty, |$a:ident| $body:expr) => {
+        impl std::ops::Neg for &$type {
+            type Output = $type;
+
+            fn neg(self) -> $type {
+                let $a = self;
+                $body
+            }
+        }
+
+        impl std::ops::Neg for $type {
+            type Output = $type;
+
+            fn neg(self) -> $type {
+                -&self
+            }
+        }
+    };
+}
+
+/// `Sum` and `Product` over owned and borrowed elements, for the types with constant `$zero`
+/// and `$one`. FieldElement carries its field at runtime, so it has `PrimeField::sum` and
+/// `PrimeField::product` instead.
+macro_rules! impl_sum_product {
+    ($type:ty, $zero:expr, $one:expr) => {
+        impl std::iter::Sum for $type {
+            fn sum<I: Iterator<Item = $type>>(iter: I) -> $type {
+                iter.fold($zero, |acc, element| acc + element)
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a $type> for $type {
+            fn sum<I: Iterator<Item = &'a $type>>(iter: I) -> $type {
+                iter.fold($zero, |acc, element| acc + element)
+            }
+        }
+
+        impl std::iter::Product for $type {
+            fn product<I: Iterator<Item = $type>>(iter: I) -> $type {
+                iter.fold($one, |acc, element| acc * element)
+            }
+        }
+
+        impl<'a> std::iter::Product<&'a $type> for $type {
+            fn product<I: Iterator<Item = &'a $type>>(iter: I) -> $type {
+                iter.fold($one, |acc, element| acc * element)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_binary_op;
+pub(crate) use impl_neg;
+pub(crate) use impl_scalar_op;
+pub(crate) use impl_scalar_ops_reversed;
+pub(crate) use impl_sum_product;
@@ -0,0 +1,597 @@
+use std::sync::OnceLock;
+
+use rug::{ops::Pow, Integer};
+
+/// Bound of the trial division done before the probabilistic tests
+const TRIAL_DIVISION_BOUND: u32 = 1000;
+
+/// Bound of the trial division used to factor n - 1 when building certificates
+const CERTIFICATE_TRIAL_DIVISION_BOUND: u32 = 1 << 17;
+
+/// Iterations of Pollard's rho before giving up on splitting a composite
+const POLLARD_RHO_ITERATIONS: usize = 1 << 20;
+
+/// Witnesses that make Miller-Rabin deterministic for every n < 3.3 * 10^24, so all of u64
+const DETERMINISTIC_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Returns the primes below `limit` with the sieve of Eratosthenes
+fn primes_below(limit: u32) -> Vec<u32> {
+    let mut is_composite = vec![false; limit as usize];
+    let mut primes = vec![];
+
+    for i in 2..limit {
+        if is_composite[i as usize] {
+            continue;
+        }
+
+        primes.push(i);
+        for multiple in (i as usize * i as usize..limit as usize).step_by(i as usize) {
+            is_composite[multiple] = true;
+        }
+    }
+
+    primes
+}
+
+fn small_primes() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+
+    PRIMES.get_or_init(|| primes_below(TRIAL_DIVISION_BOUND))
+}
+
+/// Trial division by the small primes.
+/// Returns None when n has no small factor but is too large for the division to decide.
+pub fn trial_division(n: &Integer) -> Option<bool> {
+    if *n < 2 {
+        return Some(false);
+    }
+
+    for &prime in small_primes() {
+        if *n == prime {
+            return Some(true);
+        }
+        if n.is_divisible_u(prime) {
+            return Some(false);
+        }
+    }
+
+    let bound = Integer::from(TRIAL_DIVISION_BOUND);
+    if *n < bound.clone() * &bound {
+        return Some(true);
+    }
+
+    None
+}
+
+fn mul_mod_u64(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+fn pow_mod_u64(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1;
+    base %= modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod_u64(result, base, modulus);
+        }
+        base = mul_mod_u64(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// Deterministic Miller-Rabin for 64-bit numbers
+pub fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    for &base in DETERMINISTIC_BASES.iter() {
+        if n == base {
+            return true;
+        }
+        if n.is_multiple_of(base) {
+            return false;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    'witness: for &base in DETERMINISTIC_BASES.iter() {
+        let mut x = pow_mod_u64(base, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = mul_mod_u64(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Strong probable prime test to the given base, for an odd n > 2
+pub fn miller_rabin(n: &Integer, base: u32) -> bool {
+    let n_minus_one = n.clone() - 1u32;
+    let s = n_minus_one.find_one(0).unwrap();
+    let d = n_minus_one.clone() >> s;
+
+    let mut x = Integer::from(base).pow_mod(&d, n).unwrap();
+    if x == 1 || x == n_minus_one {
+        return true;
+    }
+
+    for _ in 1..s {
+        x = x.clone() * &x % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// (x / 2) mod n for an odd n
+fn half_mod(x: Integer, n: &Integer) -> Integer {
+    if x.is_odd() {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters, for an odd n > 2
+pub fn strong_lucas(n: &Integer) -> bool {
+    // There is no suitable D for perfect squares
+    if n.is_perfect_square() {
+        return false;
+    }
+
+    // Find the first D in 5, -7, 9, -11, ... with Jacobi symbol (D/n) = -1
+    let mut d = Integer::from(5);
+    loop {
+        match d.jacobi(n) {
+            -1 => break,
+            0 if d.clone().abs() != *n => return false,
+            _ => {}
+        }
+
+        d = if d > 0 { -(d + 2u32) } else { -(d - 2u32) };
+    }
+
+    // P = 1 and Q = (1 - D) / 4
+    let q = (Integer::from(1) - &d) / 4u32;
+
+    // n + 1 = k * 2^s with k odd
+    let n_plus_one = n.clone() + 1u32;
+    let s = n_plus_one.find_one(0).unwrap();
+    let k = n_plus_one >> s;
+
+    // Compute U_k, V_k and Q^k with the binary method, starting from U_1 = 1, V_1 = P = 1
+    let mut u = Integer::from(1);
+    let mut v = Integer::from(1);
+    let mut q_k = Integer::from(q.modulo_ref(n));
+
+    for i in (0..k.significant_bits() - 1).rev() {
+        // Doubling: U_2j = U_j * V_j and V_2j = V_j^2 - 2 * Q^j
+        u = u * &v % n;
+        v = (v.clone() * &v - Integer::from(2) * &q_k).modulo(n);
+        q_k = q_k.clone() * &q_k % n;
+
+        if k.get_bit(i) {
+            // Increment: U_j+1 = (P * U_j + V_j) / 2 and V_j+1 = (D * U_j + P * V_j) / 2
+            let next_u = half_mod((u.clone() + &v).modulo(n), n);
+            let next_v = half_mod((d.clone() * &u + &v).modulo(n), n);
+            u = next_u;
+            v = next_v;
+            q_k = (q_k * &q).modulo(n);
+        }
+    }
+
+    if u == 0 || v == 0 {
+        return true;
+    }
+
+    // V_(k * 2^r) = 0 for some 0 < r < s
+    for _ in 1..s {
+        v = (v.clone() * &v - Integer::from(2) * &q_k).modulo(n);
+        if v == 0 {
+            return true;
+        }
+        q_k = q_k.clone() * &q_k % n;
+    }
+
+    false
+}
+
+/// Primality test without false positives for n < 2^64, and Baillie-PSW above.
+///
+/// Small numbers are settled by trial division, 64-bit numbers by deterministic Miller-Rabin,
+/// and larger numbers must pass both a base 2 Miller-Rabin and a strong Lucas test.
+/// No composite passing BPSW is known.
+pub fn is_prime(n: &Integer) -> bool {
+    if let Some(result) = trial_division(n) {
+        return result;
+    }
+
+    if let Some(small) = n.to_u64() {
+        return is_prime_u64(small);
+    }
+
+    miller_rabin(n, 2) && strong_lucas(n)
+}
+
+/// Tries to find a non-trivial factor of the composite n with Brent's variant of Pollard's rho
+fn pollard_rho(n: &Integer) -> Option<Integer> {
+    if n.is_even() {
+        return Some(Integer::from(2));
+    }
+
+    for c in 1u32..4 {
+        let step = |x: &Integer| (x.clone() * x + c) % n;
+        let mut x = Integer::from(2);
+        let mut y = x.clone();
+        let mut product = Integer::from(1);
+        let mut iterations = 0;
+        let mut power = 1;
+
+        while iterations < POLLARD_RHO_ITERATIONS {
+            // Move the tortoise to the hare every power of two steps
+            if iterations == power {
+                x = y.clone();
+                power *= 2;
+            }
+
+            y = step(&y);
+            product = product * (x.clone() - &y).abs() % n;
+            iterations += 1;
+
+            // Take the gcd only every 128 steps, a gcd of n means this c failed
+            if iterations % 128 == 0 || iterations == POLLARD_RHO_ITERATIONS {
+                let divisor = product.clone().gcd(n);
+                if divisor == *n {
+                    break;
+                }
+                if divisor > 1 {
+                    return Some(divisor);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// The prime factorization of n > 0 as (prime, exponent) pairs, smallest prime first.
+/// Uses trial division and Pollard's rho, returns None if a composite factor could not be split.
+pub fn factorize(n: &Integer) -> Option<Vec<(Integer, u32)>> {
+    assert!(*n > 0, "Only positive numbers can be factored");
+
+    let mut primes = vec![];
+    let mut cofactor = n.clone();
+    for &prime in small_primes() {
+        if Integer::from(prime) * prime > cofactor {
+            break;
+        }
+        while cofactor.is_divisible_u(prime) {
+            cofactor /= prime;
+            primes.push(Integer::from(prime));
+        }
+    }
+
+    let mut composites = vec![cofactor];
+    while let Some(composite) = composites.pop() {
+        if composite == 1 {
+            continue;
+        }
+
+        if is_prime(&composite) {
+            primes.push(composite);
+        } else {
+            let divisor = pollard_rho(&composite)?;
+            composites.push(composite / &divisor);
+            composites.push(divisor);
+        }
+    }
+    primes.sort();
+
+    let mut factors: Vec<(Integer, u32)> = vec![];
+    for prime in primes {
+        match factors.last_mut() {
+            Some((last, exponent)) if *last == prime => *exponent += 1,
+            _ => factors.push((prime, 1)),
+        }
+    }
+    Some(factors)
+}
+
+/// A proof that a number is prime, which can be checked without trusting the test
+/// that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Certificate {
+    /// n < 2^64, where deterministic Miller-Rabin is a proof
+    Small(u64),
+    /// Pocklington's criterion: if n - 1 = F * R, where F > sqrt(n) is completely factored,
+    /// and for each prime q dividing F there is an a with a^(n-1) = 1 (mod n) and
+    /// gcd(a^((n-1)/q) - 1, n) = 1, then n is prime.
+    /// When F = n - 1 this is a Pratt certificate.
+    Pocklington {
+        n: Integer,
+        factors: Vec<FactorWitness>,
+    },
+}
+
+/// A prime factor q^exponent of n - 1, with its Pocklington witness and its own certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactorWitness {
+    pub prime: Integer,
+    pub exponent: u32,
+    pub witness: Integer,
+    pub certificate: Certificate,
+}
+
+impl Certificate {
+    /// The number proven prime
+    pub fn number(&self) -> Integer {
+        match self {
+            Certificate::Small(n) => Integer::from(*n),
+            Certificate::Pocklington { n, .. } => n.clone(),
+        }
+    }
+
+    /// Generates a certificate, factoring n - 1 with trial division and Pollard's rho.
+    /// Returns None if n is composite, or if not enough of n - 1 could be factored.
+    pub fn generate(n: &Integer) -> Option<Certificate> {
+        Certificate::generate_with_hints(n, &[])
+    }
+
+    /// Like `generate`, with known prime factors of n - 1 (or of the q - 1 of its factors)
+    /// for the factors too large for Pollard's rho
+    pub fn generate_with_hints(n: &Integer, hints: &[Integer]) -> Option<Certificate> {
+        if !is_prime(n) {
+            return None;
+        }
+
+        if let Some(small) = n.to_u64() {
+            return Some(Certificate::Small(small));
+        }
+
+        let n_minus_one = n.clone() - 1u32;
+        let mut cofactor = n_minus_one.clone();
+        let mut primes: Vec<Integer> = vec![];
+
+        let mut divide_out = |prime: Integer, cofactor: &mut Integer| {
+            if cofactor.is_divisible(&prime) {
+                while cofactor.is_divisible(&prime) {
+                    *cofactor /= &prime;
+                }
+                primes.push(prime);
+            }
+        };
+
+        for prime in primes_below(CERTIFICATE_TRIAL_DIVISION_BOUND) {
+            divide_out(Integer::from(prime), &mut cofactor);
+        }
+        for hint in hints.iter().filter(|hint| **hint > 1 && is_prime(hint)) {
+            divide_out(hint.clone(), &mut cofactor);
+        }
+
+        // Split what is left, composites that Pollard's rho cannot split stay in R
+        let mut composites = vec![cofactor];
+        while let Some(composite) = composites.pop() {
+            if composite == 1 {
+                continue;
+            }
+
+            if is_prime(&composite) {
+                primes.push(composite);
+            } else if let Some(divisor) = pollard_rho(&composite) {
+                let quotient = composite / &divisor;
+                composites.push(divisor);
+                composites.push(quotient);
+            }
+        }
+
+        primes.sort();
+        primes.dedup();
+
+        // Certify the smallest factors first, they are the cheapest, until F > sqrt(n)
+        let mut factors = vec![];
+        let mut factored = Integer::from(1);
+        for prime in primes {
+            if factored.clone() * &factored > *n {
+                break;
+            }
+
+            let mut exponent = 0;
+            let mut remaining = n_minus_one.clone();
+            while remaining.is_divisible(&prime) {
+                remaining /= &prime;
+                exponent += 1;
+            }
+
+            let certificate = match Certificate::generate_with_hints(&prime, hints) {
+                Some(certificate) => certificate,
+                None => continue,
+            };
+            let witness = find_witness(n, &prime)?;
+
+            factored *= prime.clone().pow(exponent);
+            factors.push(FactorWitness { prime, exponent, witness, certificate });
+        }
+
+        if factored.clone() * &factored <= *n {
+            return None;
+        }
+
+        Some(Certificate::Pocklington { n: n.clone(), factors })
+    }
+
+    /// Checks the certificate, and recursively the certificates of all the factors
+    pub fn verify(&self) -> bool {
+        let (n, factors) = match self {
+            Certificate::Small(n) => return is_prime_u64(*n),
+            Certificate::Pocklington { n, factors } => (n, factors),
+        };
+
+        if *n <= 2 || n.is_even() {
+            return false;
+        }
+
+        let n_minus_one = n.clone() - 1u32;
+        let mut factored = Integer::from(1);
+
+        for factor in factors {
+            if factor.certificate.number() != factor.prime || !factor.certificate.verify() {
+                return false;
+            }
+
+            factored *= factor.prime.clone().pow(factor.exponent);
+            if !is_pocklington_witness(n, &factor.prime, &factor.witness) {
+                return false;
+            }
+        }
+
+        n_minus_one.is_divisible(&factored) && factored.clone() * &factored > *n
+    }
+}
+
+/// a^(n-1) = 1 (mod n) and gcd(a^((n-1)/q) - 1, n) = 1
+fn is_pocklington_witness(n: &Integer, prime: &Integer, witness: &Integer) -> bool {
+    let n_minus_one = n.clone() - 1u32;
+
+    let fermat = match witness.pow_mod_ref(&n_minus_one, n) {
+        Some(result) => Integer::from(result),
+        None => return false,
+    };
+    if fermat != 1 {
+        return false;
+    }
+
+    let exponent = n_minus_one / prime;
+    let power = Integer::from(witness.pow_mod_ref(&exponent, n).unwrap());
+    (power - 1u32).gcd(n) == 1
+}
+
+fn find_witness(n: &Integer, prime: &Integer) -> Option<Integer> {
+    (2u32..1000)
+        .map(Integer::from)
+        .find(|witness| is_pocklington_witness(n, prime, witness))
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::{integer::IsPrime, ops::Pow, Integer};
+
+    use super::{factorize, is_prime, is_prime_u64, strong_lucas, Certificate, FactorWitness};
+
+    fn secp256k1_prime() -> Integer {
+        Integer::from(2).pow(256) - Integer::from(2).pow(32) - Integer::from(977)
+    }
+
+    fn secp256k1_order() -> Integer {
+        Integer::from_str_radix("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16).unwrap()
+    }
+
+    #[test]
+    fn test_small_numbers() {
+        for n in 0..20000 {
+            let expected = Integer::from(n).is_probably_prime(30) != IsPrime::No;
+            assert_eq!(is_prime(&Integer::from(n)), expected, "Wrong result for {}", n);
+            assert_eq!(is_prime_u64(n as u64), expected, "Wrong result for {}", n);
+        }
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // Carmichael numbers and strong pseudoprimes to base 2
+        for n in [561u64, 41041, 825265, 2047, 3215031751, 3825123056546413051] {
+            assert!(!is_prime(&Integer::from(n)), "{} is composite", n);
+        }
+
+        // Strong Lucas pseudoprimes are caught by the Miller-Rabin half of BPSW
+        for n in [5459u32, 5777, 10877, 16109, 18971] {
+            assert!(strong_lucas(&Integer::from(n)));
+            assert!(!is_prime(&Integer::from(n)));
+        }
+
+        // Product of two primes just above 2^64
+        let p = Integer::from(2).pow(64) + 13;
+        let q = Integer::from(2).pow(64) + 37;
+        assert!(is_prime(&p) && is_prime(&q));
+        assert!(!is_prime(&(p * q)));
+    }
+
+    #[test]
+    fn test_large_primes() {
+        assert!(is_prime(&secp256k1_prime()));
+        assert!(is_prime(&secp256k1_order()));
+        assert!(is_prime(&(Integer::from(2).pow(127) - 1)));
+        assert!(is_prime(&(Integer::from(2).pow(521) - 1)));
+
+        assert!(!is_prime(&(secp256k1_prime() + 2)));
+        assert!(!is_prime(&(Integer::from(2).pow(256) - 1)));
+        assert!(!is_prime(&(Integer::from(2).pow(67) - 1)));
+    }
+
+    #[test]
+    fn test_factorize() {
+        let factors = |pairs: &[(u64, u32)]| pairs.iter().map(|&(p, e)| (Integer::from(p), e)).collect::<Vec<_>>();
+
+        assert_eq!(factorize(&Integer::from(1)), Some(vec![]));
+        assert_eq!(factorize(&Integer::from(360)), Some(factors(&[(2, 3), (3, 2), (5, 1)])));
+        assert_eq!(factorize(&Integer::from(1009u32 * 1009)), Some(factors(&[(1009, 2)])));
+        assert_eq!(factorize(&(Integer::from(2).pow(67) - 1)), Some(factors(&[(193707721, 1), (761838257287, 1)])));
+        assert_eq!(factorize(&secp256k1_order()), Some(vec![(secp256k1_order(), 1)]));
+    }
+
+    #[test]
+    fn test_certificate() {
+        assert_eq!(Certificate::generate(&Integer::from(221)), None);
+
+        let certificate = Certificate::generate(&(Integer::from(2).pow(89) - 1)).unwrap();
+        assert!(certificate.verify());
+
+        // Tampering with a witness breaks the certificate
+        if let Certificate::Pocklington { n, mut factors } = certificate {
+            factors[0] = FactorWitness { witness: Integer::from(1), ..factors[0].clone() };
+            assert!(!Certificate::Pocklington { n, factors }.verify());
+        }
+
+        // A certificate for a composite number never verifies
+        let fake = Certificate::Pocklington {
+            n: Integer::from(2).pow(67) - 1,
+            factors: vec![],
+        };
+        assert!(!fake.verify());
+    }
+
+    #[test]
+    fn test_secp256k1_certificates() {
+        // The prime factors of p - 1 and n - 1 that are too large for Pollard's rho
+        let prime_hints = [
+            Integer::from_str_radix("255515944373312847190720520512484175977", 10).unwrap(),
+        ];
+        let order_hints = [
+            Integer::from_str_radix("174723607534414371449", 10).unwrap(),
+            Integer::from_str_radix("341948486974166000522343609283189", 10).unwrap(),
+        ];
+
+        let certificate = Certificate::generate_with_hints(&secp256k1_prime(), &prime_hints).unwrap();
+        assert_eq!(certificate.number(), secp256k1_prime());
+        assert!(certificate.verify());
+
+        let certificate = Certificate::generate_with_hints(&secp256k1_order(), &order_hints).unwrap();
+        assert_eq!(certificate.number(), secp256k1_order());
+        assert!(certificate.verify());
+    }
+}
@@ -0,0 +1,118 @@
+use std::{ops::{Add, Mul}, sync::Arc};
+
+use rug::{ops::RemRounding, Integer};
+
+use crate::{primality, FieldElement, FieldError};
+
+/// The prime field GF(p) that FieldElements live in.
+///
+/// The modulus is checked for primality once, when the field is created. The field is
+/// shared between its elements through an `Arc`, so creating elements from it and doing
+/// arithmetic on them never re-tests the modulus.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PrimeField {
+    prime: Integer,
+}
+
+impl PrimeField {
+    /// Creates a new field, returning an error if `prime` is not a prime number
+    pub fn new(prime: Integer) -> Result<Arc<PrimeField>, FieldError> {
+        if !primality::is_prime(&prime) {
+            return Err(FieldError::NonPrimeModulus(prime));
+        }
+
+        Ok(Arc::new(PrimeField { prime }))
+    }
+
+    pub fn prime(&self) -> &Integer {
+        &self.prime
+    }
+
+    /// The number of bytes needed to encode any element of the field
+    pub fn byte_length(&self) -> usize {
+        (self.prime.significant_bits() as usize).div_ceil(8)
+    }
+
+    /// Creates an element of this field, `num` is reduced modulo the prime
+    pub fn element(self: &Arc<Self>, num: Integer) -> FieldElement {
+        FieldElement::from_field(num.rem_euc(&self.prime), Arc::clone(self))
+    }
+
+    pub fn zero(self: &Arc<Self>) -> FieldElement {
+        self.element(Integer::ZERO)
+    }
+
+    pub fn one(self: &Arc<Self>) -> FieldElement {
+        self.element(Integer::from(1))
+    }
+
+    /// The sum of owned or borrowed elements of the field, zero if there are none
+    pub fn sum<T>(self: &Arc<Self>, elements: impl IntoIterator<Item = T>) -> FieldElement
+    where
+        FieldElement: Add<T, Output = FieldElement>,
+    {
+        elements.into_iter().fold(self.zero(), |acc, element| acc + element)
+    }
+
+    /// The product of owned or borrowed elements of the field, one if there are none
+    pub fn product<T>(self: &Arc<Self>, elements: impl IntoIterator<Item = T>) -> FieldElement
+    where
+        FieldElement: Mul<T, Output = FieldElement>,
+    {
+        elements.into_iter().fold(self.one(), |acc, element| acc * element)
+    }
+
+    /// Two fields are the same if they share the same context, or failing that, the same modulus
+    pub fn is_same(this: &Arc<Self>, other: &Arc<Self>) -> bool {
+        Arc::ptr_eq(this, other) || this.prime == other.prime
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rug::Integer;
+
+    use crate::{FieldElement, FieldError};
+
+    use super::PrimeField;
+
+    #[test]
+    fn test_prime_field() {
+        assert_eq!(
+            PrimeField::new(Integer::from(20)),
+            Err(FieldError::NonPrimeModulus(Integer::from(20)))
+        );
+
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let a = field.element(Integer::from(24));
+        let b = field.element(Integer::from(50));
+
+        assert_eq!(b.num(), Integer::from(19));
+        assert!(Arc::ptr_eq(a.field(), field.zero().field()));
+
+        // Results of the arithmetic stay in the same field context
+        let c = a.clone() * b;
+        assert!(Arc::ptr_eq(c.field(), &field));
+        assert_eq!(c, FieldElement::new(Integer::from(22), Integer::from(31)));
+        assert_eq!(field.one() - field.one(), field.zero());
+    }
+
+    #[test]
+    fn test_same_field() {
+        let field = PrimeField::new(Integer::from(31)).unwrap();
+        let other = PrimeField::new(Integer::from(31)).unwrap();
+        let mismatched = PrimeField::new(Integer::from(29)).unwrap();
+
+        assert!(PrimeField::is_same(&field, &field));
+        assert!(PrimeField::is_same(&field, &other));
+        assert!(!PrimeField::is_same(&field, &mismatched));
+
+        assert_eq!(field.element(Integer::from(3)), other.element(Integer::from(3)));
+        assert_eq!(
+            field.one().checked_add(&mismatched.one()),
+            Err(FieldError::MismatchedModuli(Integer::from(31), Integer::from(29)))
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use rand::{CryptoRng, RngCore};
+use rug::{ops::Pow, Integer, Rational};
+
+use crate::Field;
+
+/// The field of rational numbers, with exact arithmetic.
+/// It is infinite, so it is mostly useful to check curve formulas against the real-valued
+/// pictures before moving to a finite field.
+impl Field for Rational {
+    fn zero(&self) -> Rational {
+        Rational::new()
+    }
+
+    fn one(&self) -> Rational {
+        Rational::from(1)
+    }
+
+    fn element(&self, num: &Integer) -> Rational {
+        Rational::from(num)
+    }
+
+    fn inv(&self) -> Option<Rational> {
+        if *self == 0 {
+            return None;
+        }
+
+        Some(self.clone().recip())
+    }
+
+    fn pow(&self, exponent: &Integer) -> Rational {
+        let exponent = exponent.to_u32().expect("Exponent must be a non-negative u32");
+        self.clone().pow(exponent)
+    }
+
+    /// The square root if both the numerator and the denominator are perfect squares
+    fn sqrt(&self) -> Option<Rational> {
+        let (numer, denom) = (self.numer(), self.denom());
+        if !numer.is_perfect_square() || !denom.is_perfect_square() {
+            return None;
+        }
+
+        Some(Rational::from((numer.clone().sqrt(), denom.clone().sqrt())))
+    }
+
+    /// There is no uniform distribution on the rationals, this draws the numerator
+    /// from [-2^32, 2^32) and the denominator from [1, 2^32]
+    fn random<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> Rational {
+        let numer = (rng.next_u64() as i64) >> 31;
+        let denom = u64::from(rng.next_u32()) + 1;
+
+        Rational::from((numer, denom))
+    }
+}
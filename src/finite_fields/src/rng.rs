@@ -0,0 +1,39 @@
+// Random number generators for field elements and scalars.
+//
+// Everything random in the crate takes a `RngCore + CryptoRng`, so a Mersenne twister or
+// any other predictable generator is rejected at compile time. Use `OsRng` for keys and
+// nonces, and `deterministic` in tests that need reproducible values.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+pub use rand::rngs::OsRng;
+pub use rand::{CryptoRng, RngCore};
+
+/// The deterministic generator returned by `deterministic`
+pub type DeterministicRng = ChaCha20Rng;
+
+/// A ChaCha20 generator seeded from `seed`, the same seed always gives the same values.
+/// Only meant for tests, the seed space is far too small to keep a secret.
+pub fn deterministic(seed: u64) -> DeterministicRng {
+    ChaCha20Rng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::{deterministic, OsRng};
+
+    #[test]
+    fn test_deterministic() {
+        let (mut first, mut second) = (deterministic(7), deterministic(7));
+        assert_eq!(first.next_u64(), second.next_u64());
+        assert_ne!(first.next_u64(), deterministic(8).next_u64());
+
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        OsRng.fill_bytes(&mut a);
+        OsRng.fill_bytes(&mut b);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Debug;
+
+use rand::{CryptoRng, RngCore};
+use rug::{ops::RemRounding, Integer};
+
+use crate::{
+    ct::{self, Choice, ConstantTime},
+    limbs::{self, Limbs},
+    ops::{impl_binary_op, impl_neg, impl_scalar_op, impl_scalar_ops_reversed, impl_sum_product},
+};
+
+/// n, the order of the secp256k1 generator point
+const MODULUS: Limbs = [
+    0xBFD2_5E8C_D036_4141,
+    0xBAAE_DCE6_AF48_A03B,
+    0xFFFF_FFFF_FFFF_FFFE,
+    0xFFFF_FFFF_FFFF_FFFF,
+];
+
+/// 2^256 - n, a 129-bit number
+const COMPLEMENT: [u64; 3] = [0x402D_A173_2FC9_BEBF, 0x4551_2319_50B7_5FC4, 0x1];
+
+/// n - 2, the exponent of the inverse by Fermat's little theorem
+const MODULUS_MINUS_TWO: Limbs = [MODULUS[0] - 2, MODULUS[1], MODULUS[2], MODULUS[3]];
+
+/// (n - 1) / 2, scalars above it are "high" in the sense of BIP 62 low-S signatures
+const HALF_MODULUS: Limbs = [
+    0xDFE9_2F46_681B_20A0,
+    0x5D57_6E73_57A4_501D,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0x7FFF_FFFF_FFFF_FFFF,
+];
+
+/// A secp256k1 scalar, i.e an integer modulo the order n of the generator point.
+///
+/// Like Fe256 it lives on the stack. n is also just below 2^256, so reduction folds the
+/// high half of a product back with 2^256 = 2^256 - n (mod n), which takes a few rounds
+/// because that complement is 129 bits wide.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Scalar(Limbs);
+
+impl Scalar {
+    pub const ZERO: Scalar = Scalar([0, 0, 0, 0]);
+    pub const ONE: Scalar = Scalar([1, 0, 0, 0]);
+
+    /// The group order n as an Integer
+    pub fn modulus() -> Integer {
+        limbs::to_integer(&MODULUS)
+    }
+
+    pub fn from_u64(num: u64) -> Scalar {
+        Scalar([num, 0, 0, 0])
+    }
+
+    /// Creates a scalar from any Integer, reducing it modulo n
+    pub fn from_integer(num: &Integer) -> Scalar {
+        Scalar(limbs::from_integer(&num.clone().rem_euc(&Scalar::modulus())))
+    }
+
+    pub fn to_integer(&self) -> Integer {
+        limbs::to_integer(&self.0)
+    }
+
+    /// Parses a 32-byte big-endian number, returning None if it is not below n
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+        let num = limbs::from_be_bytes(bytes);
+        if limbs::geq(&num, &MODULUS) {
+            return None;
+        }
+
+        Some(Scalar(num))
+    }
+
+    /// Parses a 32-byte big-endian number, reducing it modulo n (e.g for message hashes)
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Scalar {
+        let num = limbs::from_be_bytes(bytes);
+        if limbs::geq(&num, &MODULUS) {
+            return Scalar(limbs::sub(&num, &MODULUS).0);
+        }
+
+        Scalar(num)
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        limbs::to_be_bytes(&self.0)
+    }
+
+    /// A uniformly random scalar, by rejecting the 32-byte strings that are not below n
+    pub fn random<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Scalar {
+        let mut bytes = [0u8; 32];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            if let Some(scalar) = Scalar::from_be_bytes(&bytes) {
+                return scalar;
+            }
+        }
+    }
+
+    /// A uniformly random scalar in [1, n), e.g a private key or a nonce.
+    /// Pass `rng::OsRng` unless the value must be reproducible.
+    pub fn random_nonzero<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Scalar {
+        loop {
+            let scalar = Scalar::random(rng);
+            if !scalar.is_zero() {
+                return scalar;
+            }
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.0)
+    }
+
+    /// Returns true if the scalar is above (n - 1) / 2
+    pub fn is_high(&self) -> bool {
+        !limbs::geq(&HALF_MODULUS, &self.0)
+    }
+
+    /// Raises the scalar to a non-negative power
+    pub fn pow(&self, exponent: &Integer) -> Scalar {
+        Scalar(limbs::pow_mod(&self.0, exponent, &COMPLEMENT, &MODULUS))
+    }
+
+    /// The multiplicative inverse, or None for zero
+    pub fn invert(&self) -> Option<Scalar> {
+        if self.is_zero() {
+            return None;
+        }
+
+        Some(Scalar(limbs::invert_mod(&self.0, &MODULUS)))
+    }
+}
+
+/// Constant-time arithmetic, for secret values. See the `ct` module.
+impl Scalar {
+    pub fn ct_add(&self, other: &Scalar) -> Scalar {
+        Scalar(ct::add_mod(&self.0, &other.0, &MODULUS))
+    }
+
+    pub fn ct_sub(&self, other: &Scalar) -> Scalar {
+        Scalar(ct::sub_mod(&self.0, &other.0, &MODULUS))
+    }
+
+    pub fn ct_neg(&self) -> Scalar {
+        Scalar::ZERO.ct_sub(self)
+    }
+
+    pub fn ct_mul(&self, other: &Scalar) -> Scalar {
+        Scalar(ct::mul_mod(&self.0, &other.0, &COMPLEMENT, &MODULUS))
+    }
+
+    pub fn ct_is_zero(&self) -> Choice {
+        self.ct_eq(&Scalar::ZERO)
+    }
+
+    /// The inverse as self^(n-2), with the same work for every value. Zero maps to zero.
+    pub fn ct_invert(&self) -> Scalar {
+        Scalar(ct::pow_mod(&self.0, &MODULUS_MINUS_TWO, &COMPLEMENT, &MODULUS))
+    }
+}
+
+impl ConstantTime for Scalar {
+    fn ct_eq(&self, other: &Scalar) -> Choice {
+        ct::eq(&self.0, &other.0)
+    }
+
+    fn ct_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+        Scalar(ct::select(&a.0, &b.0, choice))
+    }
+}
+
+impl Debug for Scalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Scalar({})", self.to_integer().to_string_radix(16))
+    }
+}
+
+impl_binary_op!(Scalar, Add, add, AddAssign, add_assign, |a, b| Scalar(limbs::add_mod(&a.0, &b.0, &MODULUS)));
+impl_binary_op!(Scalar, Sub, sub, SubAssign, sub_assign, |a, b| Scalar(limbs::sub_mod(&a.0, &b.0, &MODULUS)));
+impl_binary_op!(Scalar, Mul, mul, MulAssign, mul_assign, |a, b| {
+    Scalar(limbs::mul_mod(&a.0, &b.0, &COMPLEMENT, &MODULUS))
+});
+
+impl_neg!(Scalar, |a| Scalar::ZERO - a);
+
+impl_scalar_op!(Scalar, u64, Add, add, AddAssign, add_assign, |a, n| a + Scalar::from_u64(n));
+impl_scalar_op!(Scalar, u64, Sub, sub, SubAssign, sub_assign, |a, n| a - Scalar::from_u64(n));
+impl_scalar_op!(Scalar, u64, Mul, mul, MulAssign, mul_assign, |a, n| a * Scalar::from_u64(n));
+impl_scalar_ops_reversed!(Scalar, u64);
+
+impl_sum_product!(Scalar, Scalar::ZERO, Scalar::ONE);
+
+#[cfg(test)]
+mod tests {
+    use rug::{ops::Pow, Integer};
+
+    use crate::rng::{self, OsRng};
+
+    use super::{limbs, Scalar, COMPLEMENT, HALF_MODULUS};
+
+    fn order() -> Integer {
+        Integer::from_str_radix("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16).unwrap()
+    }
+
+    #[test]
+    fn test_modulus() {
+        assert_eq!(Scalar::modulus(), order());
+        let complement = [COMPLEMENT[0], COMPLEMENT[1], COMPLEMENT[2], 0];
+        assert_eq!(limbs::to_integer(&complement), Integer::from(2).pow(256) - order());
+        assert_eq!(limbs::to_integer(&HALF_MODULUS), (order() - 1) / 2);
+        assert_eq!(Scalar::from_integer(&order()), Scalar::ZERO);
+    }
+
+    #[test]
+    fn test_arithmetic_matches_integers() {
+        let n = order();
+        let values = [
+            Integer::from(0),
+            Integer::from(1),
+            n.clone() - 1,
+            n.clone() - Integer::from(2).pow(130),
+            Integer::from_str_radix("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d", 16).unwrap(),
+            Integer::from_str_radix("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6", 16).unwrap(),
+        ];
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let (sa, sb) = (Scalar::from_integer(a), Scalar::from_integer(b));
+
+                assert_eq!((sa + sb).to_integer(), (a.clone() + b) % &n);
+                assert_eq!((sa - sb).to_integer(), (a.clone() - b + &n) % &n);
+                assert_eq!((sa * sb).to_integer(), (a.clone() * b) % &n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invert_and_high() {
+        assert_eq!(Scalar::ZERO.invert(), None);
+
+        let s = Scalar::from_integer(&Integer::from_str_radix("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6", 16).unwrap());
+        assert_eq!(s * s.invert().unwrap(), Scalar::ONE);
+
+        let half = Scalar::from_integer(&((order() - 1) / 2));
+        assert!(!half.is_high());
+        assert!((half + Scalar::ONE).is_high());
+        assert!(s.is_high());
+        assert!(!(-s).is_high());
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(Scalar::from_be_bytes(&[0xff; 32]), None);
+        assert_eq!(
+            Scalar::from_be_bytes_reduced(&[0xff; 32]).to_integer(),
+            (Integer::from(2).pow(256) - 1) % order()
+        );
+
+        let s = Scalar::from_u64(5001);
+        assert_eq!(Scalar::from_be_bytes(&s.to_be_bytes()), Some(s));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rng::deterministic(42);
+        let scalars: Vec<Scalar> = (0..50).map(|_| Scalar::random_nonzero(&mut rng)).collect();
+
+        for scalar in scalars.iter() {
+            assert!(!scalar.is_zero());
+            assert!(scalar.to_integer() < order());
+        }
+        // The same seed replays the same scalars
+        assert_eq!(Scalar::random_nonzero(&mut rng::deterministic(42)), scalars[0]);
+
+        let key = Scalar::random_nonzero(&mut OsRng);
+        assert_ne!(key, Scalar::random_nonzero(&mut OsRng));
+    }
+}
@@ -0,0 +1,184 @@
+use std::{fmt::Formatter, sync::Arc};
+
+use rug::{integer::Order, Integer};
+use serde::{
+    de::{DeserializeSeed, Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{helpers, Fe256, FieldElement, FieldError, PrimeField};
+
+/// The serialized form of a FieldElement, both numbers as big-endian hex strings.
+/// `num` is padded to the byte length of the prime, as in `FieldElement::to_hex`.
+#[derive(Serialize, Deserialize)]
+struct EncodedFieldElement {
+    prime: String,
+    num: String,
+}
+
+impl Serialize for FieldElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let encoded = EncodedFieldElement {
+            prime: helpers::to_hex(&self.field().prime().to_digits::<u8>(Order::Msf)),
+            num: self.to_hex(),
+        };
+
+        encoded.serialize(serializer)
+    }
+}
+
+/// Primes above this size are rejected before the primality test, which would otherwise run
+/// on a modulus of any size chosen by the sender
+pub const MAX_PRIME_BITS: u32 = 1024;
+
+/// Rejects a prime that is not prime or longer than `MAX_PRIME_BITS`, and a number that is
+/// not below it. Every element gets its own field, except for the secp256k1 prime which
+/// reuses `Fe256::field()`: use `ElementSeed` when the field is known in advance.
+impl<'de> Deserialize<'de> for FieldElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<FieldElement, D::Error> {
+        let encoded = EncodedFieldElement::deserialize(deserializer)?;
+
+        let prime = Integer::from_digits(&helpers::from_hex(&encoded.prime).map_err(D::Error::custom)?, Order::Msf);
+        if prime.significant_bits() > MAX_PRIME_BITS {
+            return Err(D::Error::custom(format!("The prime has more than {} bits", MAX_PRIME_BITS)));
+        }
+        let field = if prime == Fe256::modulus() {
+            Fe256::field()
+        } else {
+            PrimeField::new(prime).map_err(D::Error::custom)?
+        };
+
+        FieldElement::from_hex(&field, &encoded.num).map_err(D::Error::custom)
+    }
+}
+
+/// Deserializes an element of a known field, which the element then shares. The encoded
+/// prime must be the prime of the field, it is compared but never tested for primality.
+pub struct ElementSeed<'a>(pub &'a Arc<PrimeField>);
+
+impl<'de> DeserializeSeed<'de> for ElementSeed<'_> {
+    type Value = FieldElement;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<FieldElement, D::Error> {
+        let encoded = EncodedFieldElement::deserialize(deserializer)?;
+
+        let prime = Integer::from_digits(&helpers::from_hex(&encoded.prime).map_err(D::Error::custom)?, Order::Msf);
+        if prime != *self.0.prime() {
+            return Err(D::Error::custom(FieldError::MismatchedModuli(prime, self.0.prime().clone())));
+        }
+
+        FieldElement::from_hex(self.0, &encoded.num).map_err(D::Error::custom)
+    }
+}
+
+/// Deserializes a sequence of elements of a known field, see `ElementSeed`
+pub struct ElementsSeed<'a>(pub &'a Arc<PrimeField>);
+
+impl<'de> DeserializeSeed<'de> for ElementsSeed<'_> {
+    type Value = Vec<FieldElement>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<FieldElement>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ElementsSeed<'_> {
+    type Value = Vec<FieldElement>;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a sequence of field elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<FieldElement>, A::Error> {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element_seed(ElementSeed(self.0))? {
+            elements.push(element);
+        }
+        Ok(elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rug::{ops::Pow, Integer};
+    use serde::de::DeserializeSeed;
+
+    use crate::{Fe256, FieldElement, PrimeField};
+
+    use super::{ElementSeed, ElementsSeed};
+
+    #[test]
+    fn test_json_round_trip() {
+        let field = PrimeField::new(Integer::from(223)).unwrap();
+        let element = field.element(Integer::from(17));
+
+        let json = serde_json::to_string(&element).unwrap();
+        assert_eq!(json, r#"{"prime":"df","num":"11"}"#);
+        assert_eq!(serde_json::from_str::<FieldElement>(&json).unwrap(), element);
+
+        let elements = vec![Fe256::field().element(Integer::from(5000)), Fe256::field().zero()];
+        let json = serde_json::to_string(&elements).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<FieldElement>>(&json).unwrap(), elements);
+    }
+
+    #[test]
+    fn test_json_rejects_invalid() {
+        let invalid = [
+            // Not below the modulus
+            r#"{"prime":"df","num":"df"}"#,
+            r#"{"prime":"df","num":"ff"}"#,
+            // 221 = 13 * 17 is not prime
+            r#"{"prime":"dd","num":"11"}"#,
+            // Wrong length and bad hex
+            r#"{"prime":"df","num":"0011"}"#,
+            r#"{"prime":"df","num":"1g"}"#,
+            r#"{"prime":"df"}"#,
+        ];
+
+        for json in invalid {
+            assert!(serde_json::from_str::<FieldElement>(json).is_err(), "{} should be rejected", json);
+        }
+
+        let error = serde_json::from_str::<FieldElement>(r#"{"prime":"df","num":"e0"}"#).unwrap_err();
+        assert!(error.to_string().contains("Value 224 is not below the modulus 223"));
+    }
+
+    #[test]
+    fn test_json_shares_fields() {
+        // secp256k1 elements all get the shared field, without a primality test each
+        let elements = vec![Fe256::field().element(Integer::from(5000)), Fe256::field().one()];
+        let json = serde_json::to_string(&elements).unwrap();
+        for element in serde_json::from_str::<Vec<FieldElement>>(&json).unwrap() {
+            assert!(Arc::ptr_eq(element.field(), &Fe256::field()));
+        }
+
+        // A known field is shared by every element deserialized with its seed
+        let field = PrimeField::new(Integer::from(223)).unwrap();
+        let elements: Vec<FieldElement> = (0..5).map(|num| field.element(Integer::from(num))).collect();
+        let json = serde_json::to_string(&elements).unwrap();
+
+        let decoded = ElementsSeed(&field).deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+        assert_eq!(decoded, elements);
+        assert!(decoded.iter().all(|element| Arc::ptr_eq(element.field(), &field)));
+
+        let single = r#"{"prime":"df","num":"11"}"#;
+        let element = ElementSeed(&field).deserialize(&mut serde_json::Deserializer::from_str(single)).unwrap();
+        assert!(Arc::ptr_eq(element.field(), &field));
+
+        // The encoded prime must be the prime of the seed
+        let other = PrimeField::new(Integer::from(227)).unwrap();
+        assert!(ElementSeed(&other).deserialize(&mut serde_json::Deserializer::from_str(single)).is_err());
+    }
+
+    #[test]
+    fn test_json_rejects_large_primes() {
+        // 2^1279 - 1 is prime, but too large to be tested on every element
+        let prime: Integer = Integer::from(2).pow(1279) - 1;
+        let json = format!(r#"{{"prime":"{}","num":"01"}}"#, prime.to_string_radix(16));
+
+        let error = serde_json::from_str::<FieldElement>(&json).unwrap_err();
+        assert!(error.to_string().contains("more than 1024 bits"));
+    }
+}
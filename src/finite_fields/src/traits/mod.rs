@@ -0,0 +1,125 @@
+use std::{fmt::Debug, ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign}};
+
+use rand::{CryptoRng, RngCore};
+use rug::Integer;
+
+/// The arithmetic of a field, so that curves and algorithms can be written once for any
+/// implementation: FieldElement, the fixed-width Fe256 or exact rationals.
+///
+/// The constructors take `&self` because an element may carry its field with it (the prime of
+/// a FieldElement is only known at runtime), `zero`, `one` and friends are in the field of self.
+///
+/// Besides the owned operators, the right operand can be borrowed (`a * &b`), and `a * n`
+/// multiplies by a small integer.
+pub trait Field:
+    Sized
+    + Clone
+    + PartialEq
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + for<'a> Add<&'a Self, Output = Self>
+    + for<'a> Sub<&'a Self, Output = Self>
+    + for<'a> Mul<&'a Self, Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + for<'a> AddAssign<&'a Self>
+    + for<'a> SubAssign<&'a Self>
+    + for<'a> MulAssign<&'a Self>
+    + Mul<u64, Output = Self>
+{
+    /// The additive identity
+    fn zero(&self) -> Self;
+
+    /// The multiplicative identity
+    fn one(&self) -> Self;
+
+    /// The image of `num` in the field
+    fn element(&self, num: &Integer) -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == self.zero()
+    }
+
+    /// The multiplicative inverse, or None for zero
+    fn inv(&self) -> Option<Self>;
+
+    /// Raises self to a non-negative power
+    fn pow(&self, exponent: &Integer) -> Self;
+
+    /// A square root of self, or None if self is not a square
+    fn sqrt(&self) -> Option<Self>;
+
+    /// A random element of the field, drawn from a cryptographically secure generator
+    fn random<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> Self;
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::{Integer, Rational};
+
+    use crate::{rng, Fe256, PrimeField};
+
+    use super::Field;
+
+    // The field axioms checked on random elements of the field of `sample`
+    fn check_field<F: Field>(sample: F) {
+        let mut rng = rng::deterministic(7);
+        let (zero, one) = (sample.zero(), sample.one());
+
+        for _ in 0..20 {
+            let a = sample.random(&mut rng);
+            let b = sample.random(&mut rng);
+            let c = sample.random(&mut rng);
+
+            assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+            assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+            assert_eq!(a.clone() * (b.clone() + c.clone()), a.clone() * b.clone() + a.clone() * c.clone());
+            assert_eq!(a.clone() + zero.clone(), a);
+            assert_eq!(a.clone() * one.clone(), a);
+            assert_eq!(a.clone() - a.clone(), zero);
+            assert_eq!(-a.clone() + &a, zero);
+            assert_eq!(a.clone() * 3, a.clone() + &a + &a);
+            assert_eq!(a.pow(&Integer::from(3)), a.clone() * a.clone() * a.clone());
+            assert_eq!(a.pow(&Integer::ZERO), one);
+
+            match a.inv() {
+                Some(inverse) => assert_eq!(a.clone() * inverse, one),
+                None => assert!(a.is_zero()),
+            }
+
+            let square = a.clone() * a.clone();
+            let root = square.sqrt().unwrap();
+            assert_eq!(root.clone() * root, square);
+        }
+
+        assert_eq!(zero.inv(), None);
+        assert_eq!(sample.element(&Integer::from(2)), one.clone() + one);
+    }
+
+    #[test]
+    fn test_field_element() {
+        check_field(PrimeField::new(Integer::from(223)).unwrap().zero());
+        check_field(PrimeField::new(Integer::from(17)).unwrap().zero());
+        check_field(Fe256::field().zero());
+    }
+
+    #[test]
+    fn test_fe256() {
+        check_field(Fe256::ZERO);
+    }
+
+    #[test]
+    fn test_rational() {
+        check_field(Rational::new());
+
+        let half = Rational::from((1, 2));
+        assert_eq!(Field::inv(&half), Some(Rational::from(2)));
+        assert_eq!(Field::sqrt(&Rational::from((9, 4))), Some(Rational::from((3, 2))));
+        assert_eq!(Field::sqrt(&Rational::from(2)), None);
+        assert_eq!(Field::sqrt(&Rational::from(-4)), None);
+    }
+}